@@ -0,0 +1,38 @@
+use qalam::error::ErrorReporter;
+use qalam::interpreter::{Interpreter, MAX_CALL_DEPTH};
+use qalam::parser::Parser;
+use qalam::resolver::Resolver;
+use qalam::scanner::Scanner;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Runs a program that recurses without end on the main thread of the host, rather than on
+// a thread with a large stack like the CLI does, and checks it ends with an error
+fn main () {
+  let source = "
+    kaam count(n) {
+      wapis 1 + count(n + 1);
+    }
+    bolo count(0);
+  ";
+
+  let mut reporter = ErrorReporter::init();
+  let mut scanner = Scanner::init(source, &mut reporter);
+  let tokens = scanner.scan_tokens();
+  let mut statements = Parser::init(tokens).parse().expect("Failed to parse program");
+
+  let interpreter = Rc::new(RefCell::new(Interpreter::init()));
+  // More calls than fit in the main thread's stack, so the stack size stops them first
+  interpreter.borrow_mut().max_call_depth = MAX_CALL_DEPTH;
+  Resolver::init(interpreter.clone())
+    .resolve_stmts(&mut statements)
+    .expect("Failed to resolve program");
+
+  let error = interpreter
+    .borrow_mut()
+    .interpret(statements)
+    .expect_err("Program should overflow the stack");
+  println!("{}", error.message);
+  assert!(error.message.starts_with("Stack overflow"), "error was {:?}", error.message);
+  println!("The host is still running");
+}
@@ -0,0 +1,145 @@
+use qalam::framing::{read_framed_message, write_framed_message};
+use serde_json::{json, Value};
+use std::io::{BufReader, Write};
+use std::process::{Command, Stdio};
+
+static EXAMPLE_NAME: &str = "lsp";
+
+// Scripted client that sends a few requests to `qalam lsp`, prints the responses and checks them
+fn main () {
+  let mut server = Command::new("cargo")
+    .arg("run")
+    .arg("--")
+    .arg("lsp")
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .spawn()
+    .expect("Failed to execute process");
+
+  let source = std::fs::read_to_string(format!("./examples/{}/main.qlm", EXAMPLE_NAME))
+    .expect("Failed to read example");
+  let uri = "file:///main.qlm";
+  let document = json!({ "uri": uri });
+
+  // No `positionEncodings` are offered, so characters are counted in UTF-16 code units
+  // and the 👋 on line 1 takes two of them
+  let messages = vec![
+    json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": { "capabilities": {} } }),
+    json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+    json!({
+      "jsonrpc": "2.0",
+      "method": "textDocument/didOpen",
+      "params": { "textDocument": { "uri": uri, "languageId": "qalam", "version": 1, "text": source } }
+    }),
+    // `naam` after the 👋
+    json!({
+      "jsonrpc": "2.0",
+      "id": 2,
+      "method": "textDocument/definition",
+      "params": { "textDocument": document, "position": { "line": 1, "character": 36 } }
+    }),
+    // `naam` at its declaration
+    json!({
+      "jsonrpc": "2.0",
+      "id": 3,
+      "method": "textDocument/references",
+      "params": {
+        "textDocument": document,
+        "position": { "line": 0, "character": 12 },
+        "context": { "includeDeclaration": true }
+      }
+    }),
+    // `len` native
+    json!({
+      "jsonrpc": "2.0",
+      "id": 4,
+      "method": "textDocument/hover",
+      "params": { "textDocument": document, "position": { "line": 6, "character": 6 } }
+    }),
+    json!({
+      "jsonrpc": "2.0",
+      "id": 5,
+      "method": "textDocument/completion",
+      "params": { "textDocument": document, "position": { "line": 6, "character": 0 } }
+    }),
+    // A syntax error is published as a diagnostic
+    json!({
+      "jsonrpc": "2.0",
+      "method": "textDocument/didChange",
+      "params": {
+        "textDocument": { "uri": uri, "version": 2 },
+        "contentChanges": [{ "text": "bolo (1;" }]
+      }
+    }),
+    json!({ "jsonrpc": "2.0", "id": 6, "method": "shutdown" }),
+    json!({ "jsonrpc": "2.0", "method": "exit" }),
+  ];
+
+  let mut stdin = server.stdin.take().unwrap();
+  // A body that isn't JSON gets a parse error and the server keeps going
+  write!(stdin, "Content-Length: 5\r\n\r\n{{oops").unwrap();
+  for message in messages.iter() {
+    write_framed_message(&mut stdin, message).unwrap();
+  }
+  drop(stdin);
+
+  let mut stdout = BufReader::new(server.stdout.take().unwrap());
+  let mut responses = Vec::new();
+  while let Some(response) = read_framed_message(&mut stdout).unwrap() {
+    println!("{}", response);
+    responses.push(response);
+  }
+
+  let status = server.wait().expect("Failed to wait for server");
+  if !status.success() {
+    eprintln!("Command failed with status: {:?}", status)
+  }
+
+  let result = |id: i64| -> &Value {
+    return &responses
+      .iter()
+      .find(|response| response["id"] == id)
+      .unwrap_or_else(|| panic!("No response to request {}", id))["result"];
+  };
+  let range = |line: i64, start: i64, end: i64| -> Value {
+    return json!({
+      "start": { "line": line, "character": start },
+      "end": { "line": line, "character": end }
+    });
+  };
+
+  assert_eq!(result(1)["capabilities"]["positionEncoding"], "utf-16");
+  assert_eq!(result(2)["range"], range(0, 11, 15));
+  let references: Vec<&Value> = result(3)
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|location| &location["range"])
+    .collect();
+  assert_eq!(references, vec![&range(0, 11, 15), &range(1, 33, 37)]);
+  let hover = result(4)["contents"]["value"].as_str().unwrap();
+  assert!(hover.contains("len("), "hover was {:?}", hover);
+  let completions: Vec<&str> = result(5)
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|item| item["label"].as_str().unwrap())
+    .collect();
+  for label in ["agar", "len", "math", "args"] {
+    assert!(completions.contains(&label), "{} is missing from {:?}", label, completions);
+  }
+  assert_eq!(responses[0]["id"], Value::Null);
+  assert_eq!(responses[0]["error"]["code"], -32700);
+
+  let diagnostics: Vec<&Value> = responses
+    .iter()
+    .filter(|response| response["method"] == "textDocument/publishDiagnostics")
+    .map(|response| &response["params"]["diagnostics"])
+    .collect();
+  assert_eq!(diagnostics.len(), 2);
+  assert_eq!(diagnostics[0], &json!([]));
+  assert_eq!(diagnostics[1].as_array().unwrap().len(), 1);
+  assert_eq!(diagnostics[1][0]["severity"], 1);
+  assert_eq!(diagnostics[1][0]["range"]["start"]["line"], 0);
+  println!("All responses are as expected");
+}
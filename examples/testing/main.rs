@@ -0,0 +1,17 @@
+use std::process::Command;
+
+static EXAMPLE_NAME: &str = "testing";
+
+fn main () {
+  let status = Command::new("cargo")
+    .arg("run")
+    .arg("--")
+    .arg("test")
+    .arg(format!("./examples/{}", EXAMPLE_NAME))
+    .status()
+    .expect("Failed to execute process");
+
+  if !status.success() {
+    eprintln!("Command failed with status: {:?}", status)
+  }
+}
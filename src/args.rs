@@ -1,17 +1,149 @@
-use clap::Parser;
+use crate::interpreter::{DEFAULT_MAX_CALL_DEPTH, MAX_CALL_DEPTH};
+use clap::{Parser, Subcommand};
 
 #[derive(Parser)]
 #[command(
     name = "qalam", 
     about="An interpreter for the Qalam language",
-    version=env!("CARGO_PKG_VERSION")
+    version=env!("CARGO_PKG_VERSION"),
+    args_conflicts_with_subcommands = true
 )]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// File path to interpret and execute
     #[arg(value_name = "FILE_PATH")]
     pub file_path: Option<String>,
 
+    /// Arguments passed to the program in the `args` array
+    #[arg(value_name = "ARGS", trailing_var_arg = true, allow_hyphen_values = true)]
+    pub script_args: Vec<String>,
+
     /// Raw string input to interpret and execute
     #[arg(long = "raw", value_name = "SOURCE")]
     pub raw: Option<String>,
+
+    /// Maximum depth of nested calls before a stack overflow error is raised, at most 4000
+    #[arg(
+        long = "max-call-depth",
+        global = true,
+        value_name = "DEPTH",
+        default_value_t = DEFAULT_MAX_CALL_DEPTH,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(..=MAX_CALL_DEPTH as u64)
+    )]
+    pub max_call_depth: usize,
+
+    /// Maximum number of statements and expressions evaluated before execution is stopped
+    #[arg(long = "max-steps", global = true, value_name = "STEPS")]
+    pub max_steps: Option<u64>,
+
+    /// Maximum time in milliseconds the program can run for
+    #[arg(long = "timeout", global = true, value_name = "MILLISECONDS")]
+    pub timeout: Option<u64>,
+
+    /// Maximum number of bytes that can be allocated for arrays and strings
+    #[arg(long = "max-memory", global = true, value_name = "BYTES")]
+    pub max_memory: Option<usize>,
+
+    /// Allow the program to read files in a directory (or a single file), can be repeated
+    #[arg(long = "allow-read", global = true, value_name = "PATH")]
+    pub allow_read: Vec<String>,
+
+    /// Allow the program to write files in a directory (or a single file), can be repeated
+    #[arg(long = "allow-write", global = true, value_name = "PATH")]
+    pub allow_write: Vec<String>,
+
+    /// Seed for the random number generator, so `random` and `random_int` give the same numbers each run
+    #[arg(long = "seed", global = true, value_name = "SEED")]
+    pub seed: Option<u64>,
+
+    /// Make runs reproducible: `clock` returns a virtual time and random numbers are seeded (with 0 unless --seed is given)
+    #[arg(long = "deterministic", global = true)]
+    pub deterministic: bool,
+
+    /// Optimise the program before running it
+    #[arg(short = 'O', long = "optimize")]
+    pub optimize: bool,
+
+    /// Print the optimised AST instead of running the program
+    #[arg(long = "show-optimized")]
+    pub show_optimized: bool,
+
+    /// Print the tokens instead of running the program
+    #[arg(long = "tokens")]
+    pub tokens: bool,
+
+    /// Print the syntax tree instead of running the program
+    #[arg(long = "ast")]
+    pub ast: bool,
+
+    /// Print the scope distance the resolver found for each variable instead of running the program
+    #[arg(long = "resolved")]
+    pub resolved: bool,
+
+    /// Print --tokens, --ast, --resolved and --show-optimized as JSON
+    #[arg(long = "json")]
+    pub json: bool,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Format .qlm files in place
+    Fmt {
+        /// Files or directories to format
+        #[arg(value_name = "PATH", required = true)]
+        paths: Vec<String>,
+
+        /// Check that files are formatted without changing them
+        #[arg(long = "check")]
+        check: bool,
+    },
+
+    /// Check .qlm files for likely mistakes
+    Lint {
+        /// Files or directories to lint
+        #[arg(value_name = "PATH", required = true)]
+        paths: Vec<String>,
+    },
+
+    /// Start a language server that communicates over stdio
+    Lsp,
+
+    /// Run the test_* functions in *_test.qlm files
+    Test {
+        /// Test files, or directories to search for *_test.qlm files
+        #[arg(value_name = "PATH", default_value = ".")]
+        paths: Vec<String>,
+
+        /// Only run tests whose name contains this
+        #[arg(short = 'f', long = "filter", value_name = "NAME")]
+        filter: Option<String>,
+    },
+
+    /// Compare the output of .qlm files with their expected output in a .out file next to them
+    Snapshot {
+        /// Files, or directories to search for .qlm files that have a .out file
+        #[arg(value_name = "PATH", default_value = ".")]
+        paths: Vec<String>,
+
+        /// Rewrite the .out files with the current output
+        #[arg(long = "update")]
+        update: bool,
+    },
+
+    /// Run a .qlm file in the step debugger
+    Debug {
+        /// File to debug, not needed with --dap since the client sends it
+        #[arg(value_name = "FILE", required_unless_present = "dap")]
+        file: Option<String>,
+
+        /// Line to set a breakpoint on, can be repeated
+        #[arg(short = 'b', long = "break", value_name = "LINE")]
+        breakpoints: Vec<i64>,
+
+        /// Serve the Debug Adapter Protocol over stdio instead of the console
+        #[arg(long = "dap")]
+        dap: bool,
+    },
 }
@@ -69,6 +69,28 @@ pub enum Expr {
 }
 
 impl Expr {
+    /// Finds the token that best represents the expression's location
+    /// Literals carry no token so `None` is returned for them
+    pub fn token(&self) -> Option<&Token> {
+        match self {
+            Self::Assign { name, .. } => Some(name),
+            Self::Binary { operator, .. } => Some(operator),
+            Self::Grouping { expression } => expression.token(),
+            Self::Literal { .. } => None,
+            Self::Unary { operator, .. } => Some(operator),
+            Self::Variable { name } => Some(name),
+            Self::Logical { operator, .. } => Some(operator),
+            Self::Call { paren, .. } => Some(paren),
+            Self::Get { name, .. } => Some(name),
+            Self::Set { name, .. } => Some(name),
+            Self::This { keyword } => Some(keyword),
+            Self::Super { keyword, .. } => Some(keyword),
+            Self::Array { values } => values.iter().find_map(|value| value.token()),
+            Self::GetIndexed { bracket, .. } => Some(bracket),
+            Self::SetIndexed { bracket, .. } => Some(bracket),
+        }
+    }
+
     pub fn accept<V>(&self, visitor: &mut V) -> V::R
     where
         V: ExprVisitor,
@@ -1,3 +1,4 @@
 pub mod expr;
+pub mod printer;
 pub mod stmt;
 pub mod visitor;
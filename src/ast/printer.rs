@@ -0,0 +1,472 @@
+use crate::ast::expr::Expr;
+use crate::ast::stmt::Stmt;
+use crate::interpreter::Interpreter;
+use crate::literal::Literal;
+use crate::token::Token;
+use serde_json::{json, Map, Value};
+
+/// A node of the syntax tree, in a form that can be printed as text or JSON
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub kind: &'static str,
+    /// The name, operator or value the node is about
+    pub text: Option<String>,
+    pub line: Option<i64>,
+    pub children: Vec<(&'static str, Child)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Child {
+    One(Node),
+    Many(Vec<Node>),
+}
+
+impl Node {
+    fn init(kind: &'static str, text: Option<String>, token: Option<&Token>) -> Self {
+        return Self {
+            kind,
+            text,
+            line: token.map(|token| token.line),
+            children: Vec::new(),
+        };
+    }
+
+    fn child(mut self, field: &'static str, node: Node) -> Self {
+        self.children.push((field, Child::One(node)));
+        return self;
+    }
+
+    fn children(mut self, field: &'static str, nodes: Vec<Node>) -> Self {
+        self.children.push((field, Child::Many(nodes)));
+        return self;
+    }
+
+    fn expr(expr: &Expr) -> Self {
+        return match expr {
+            Expr::Assign { name, value } => {
+                Self::init("Assign", Some(name.lexeme.to_string()), Some(name))
+                    .child("value", Self::expr(value))
+            }
+            Expr::Binary {
+                left,
+                operator,
+                right,
+            } => Self::init("Binary", Some(operator.lexeme.to_string()), Some(operator))
+                .child("left", Self::expr(left))
+                .child("right", Self::expr(right)),
+            Expr::Grouping { expression } => {
+                Self::init("Grouping", None, expr.token()).child("expression", Self::expr(expression))
+            }
+            Expr::Literal { value } => Self::init("Literal", Some(describe(value)), None),
+            Expr::Unary { operator, right } => {
+                Self::init("Unary", Some(operator.lexeme.to_string()), Some(operator))
+                    .child("right", Self::expr(right))
+            }
+            Expr::Variable { name } => {
+                Self::init("Variable", Some(name.lexeme.to_string()), Some(name))
+            }
+            Expr::Logical {
+                left,
+                operator,
+                right,
+            } => Self::init("Logical", Some(operator.lexeme.to_string()), Some(operator))
+                .child("left", Self::expr(left))
+                .child("right", Self::expr(right)),
+            Expr::Call {
+                callee,
+                paren,
+                arguments,
+            } => Self::init("Call", None, Some(paren))
+                .child("callee", Self::expr(callee))
+                .children("arguments", arguments.iter().map(Self::expr).collect()),
+            Expr::Get { object, name } => {
+                Self::init("Get", Some(name.lexeme.to_string()), Some(name))
+                    .child("object", Self::expr(object))
+            }
+            Expr::Set {
+                object,
+                name,
+                value,
+            } => Self::init("Set", Some(name.lexeme.to_string()), Some(name))
+                .child("object", Self::expr(object))
+                .child("value", Self::expr(value)),
+            Expr::This { keyword } => Self::init("This", None, Some(keyword)),
+            Expr::Super { keyword, method } => {
+                Self::init("Super", Some(method.lexeme.to_string()), Some(keyword))
+            }
+            Expr::Array { values } => Self::init("Array", None, expr.token())
+                .children("values", values.iter().map(Self::expr).collect()),
+            Expr::GetIndexed {
+                object,
+                index,
+                bracket,
+            } => Self::init("GetIndexed", None, Some(bracket))
+                .child("object", Self::expr(object))
+                .child("index", Self::expr(index)),
+            Expr::SetIndexed {
+                object,
+                index,
+                value,
+                bracket,
+            } => Self::init("SetIndexed", None, Some(bracket))
+                .child("object", Self::expr(object))
+                .child("index", Self::expr(index))
+                .child("value", Self::expr(value)),
+        };
+    }
+
+    fn stmt(stmt: &Stmt) -> Self {
+        return match stmt {
+            Stmt::Expression { expression } => Self::init("Expression", None, stmt.token())
+                .child("expression", Self::expr(expression)),
+            Stmt::Print {
+                keyword,
+                expression,
+            } => Self::init("Print", None, Some(keyword)).child("expression", Self::expr(expression)),
+            Stmt::Var { name, initializer } => {
+                let node = Self::init("Var", Some(name.lexeme.to_string()), Some(name));
+                match initializer {
+                    Some(initializer) => node.child("initializer", Self::expr(initializer)),
+                    None => node,
+                }
+            }
+            Stmt::Block { statements } => Self::init("Block", None, stmt.token())
+                .children("statements", statements.iter().map(Self::stmt).collect()),
+            Stmt::If {
+                condition,
+                then,
+                else_branch,
+            } => {
+                let node = Self::init("If", None, stmt.token())
+                    .child("condition", Self::expr(condition))
+                    .child("then", Self::stmt(then));
+                match else_branch {
+                    Some(else_branch) => node.child("else", Self::stmt(else_branch)),
+                    None => node,
+                }
+            }
+            Stmt::While { condition, body } => Self::init("While", None, stmt.token())
+                .child("condition", Self::expr(condition))
+                .child("body", Self::stmt(body)),
+            Stmt::Function {
+                name,
+                params,
+                defaults,
+                rest,
+                body,
+            } => {
+                let mut names: Vec<String> = params.iter().map(|param| param.lexeme.to_string()).collect();
+                if let (true, Some(last)) = (rest, names.last_mut()) {
+                    *last = format!("...{}", last);
+                }
+                let signature = format!("{}({})", name.lexeme, names.join(", "));
+                let defaults: Vec<Node> = params
+                    .iter()
+                    .zip(defaults.iter())
+                    .filter_map(|(param, default)| {
+                        let default = default.as_ref()?;
+                        Some(
+                            Self::init("Default", Some(param.lexeme.to_string()), Some(param))
+                                .child("value", Self::expr(default)),
+                        )
+                    })
+                    .collect();
+                let node = Self::init("Function", Some(signature), Some(name));
+                let node = match defaults.is_empty() {
+                    true => node,
+                    false => node.children("defaults", defaults),
+                };
+                node.children("body", body.iter().map(Self::stmt).collect())
+            }
+            Stmt::Return { keyword, value } => {
+                let node = Self::init("Return", None, Some(keyword));
+                match value {
+                    Some(value) => node.child("value", Self::expr(value)),
+                    None => node,
+                }
+            }
+            Stmt::Class {
+                name,
+                methods,
+                superclass,
+            } => {
+                let mut node = Self::init("Class", Some(name.lexeme.to_string()), Some(name));
+                if let Some(superclass) = superclass {
+                    node = node.child("superclass", Self::expr(superclass));
+                }
+                node.children("methods", methods.iter().map(Self::stmt).collect())
+            }
+        };
+    }
+
+    fn write(&self, field: Option<&str>, indent: usize, out: &mut String) {
+        out.push_str(&"  ".repeat(indent));
+        if let Some(field) = field {
+            out.push_str(&format!("{}: ", field));
+        }
+        out.push_str(self.kind);
+        if let Some(text) = &self.text {
+            out.push_str(&format!(" {}", text));
+        }
+        if let Some(line) = self.line {
+            out.push_str(&format!(" (line {})", line));
+        }
+        out.push('\n');
+        for (field, child) in self.children.iter() {
+            match child {
+                Child::One(node) => node.write(Some(field), indent + 1, out),
+                Child::Many(nodes) => {
+                    out.push_str(&format!("{}{}:\n", "  ".repeat(indent + 1), field));
+                    for node in nodes.iter() {
+                        node.write(None, indent + 2, out);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut object = Map::new();
+        object.insert(String::from("type"), json!(self.kind));
+        if let Some(text) = &self.text {
+            object.insert(String::from("text"), json!(text));
+        }
+        if let Some(line) = self.line {
+            object.insert(String::from("line"), json!(line));
+        }
+        for (field, child) in self.children.iter() {
+            let value = match child {
+                Child::One(node) => node.to_json(),
+                Child::Many(nodes) => Value::Array(nodes.iter().map(|node| node.to_json()).collect()),
+            };
+            object.insert(field.to_string(), value);
+        }
+        return Value::Object(object);
+    }
+}
+
+/// Describes a literal value, quoting strings so they can be told apart from names
+fn describe(value: &Option<Literal>) -> String {
+    return match value {
+        Some(Literal::String(val)) => format!("{:?}", val),
+        Some(Literal::Number(val)) => format!("{}", val),
+        Some(Literal::Bool(true)) => String::from("sach"),
+        Some(Literal::Bool(false)) => String::from("jhoot"),
+        Some(val) => val.to_qalam_string(),
+        None => String::from("khali"),
+    };
+}
+
+/// Converts a literal to the closest JSON value
+pub fn literal_json(value: &Option<Literal>) -> Value {
+    return match value {
+        Some(Literal::Number(val)) => json!(val.0),
+        Some(Literal::String(val)) => json!(val),
+        Some(Literal::Bool(val)) => json!(val),
+        Some(val) => json!(val.to_qalam_string()),
+        None => Value::Null,
+    };
+}
+
+/// Prints a program as an indented tree, one node per line
+pub fn ast_text(statements: &[Stmt]) -> String {
+    let mut out = String::new();
+    for stmt in statements.iter() {
+        Node::stmt(stmt).write(None, 0, &mut out);
+    }
+    return out;
+}
+
+pub fn ast_json(statements: &[Stmt]) -> Value {
+    return Value::Array(statements.iter().map(|stmt| Node::stmt(stmt).to_json()).collect());
+}
+
+/// Prints an expression as an indented tree
+pub fn expr_text(expr: &Expr) -> String {
+    let mut out = String::new();
+    Node::expr(expr).write(None, 0, &mut out);
+    return out;
+}
+
+/// Prints a token stream, one token per line with its line and column
+pub fn tokens_text(tokens: &[Token]) -> String {
+    return tokens
+        .iter()
+        .map(|token| format!("{}:{}\t{}\n", token.line, token.column, token.to_string()))
+        .collect();
+}
+
+pub fn tokens_json(tokens: &[Token]) -> Value {
+    return Value::Array(
+        tokens
+            .iter()
+            .map(|token| {
+                return json!({
+                    "type": format!("{:?}", token.token_type),
+                    "lexeme": token.lexeme,
+                    "literal": literal_json(&token.literal),
+                    "line": token.line,
+                    "column": token.column
+                });
+            })
+            .collect(),
+    );
+}
+
+/// A use of a name and how many scopes out the resolver found it, `None` for globals
+pub struct Reference {
+    pub name: Token,
+    pub depth: Option<usize>,
+}
+
+/// Lists every variable reference in a program with its resolved scope distance
+///
+/// ### Arguments
+/// `statements` - the resolved program
+/// `interpreter` - the interpreter with the scope distances recorded by the resolver
+pub fn references(statements: &[Stmt], interpreter: &Interpreter) -> Vec<Reference> {
+    let mut references = Vec::new();
+    for stmt in statements.iter() {
+        stmt_references(stmt, interpreter, &mut references);
+    }
+    references.sort_by_key(|reference| (reference.name.line, reference.name.column));
+    return references;
+}
+
+fn stmt_references(stmt: &Stmt, interpreter: &Interpreter, out: &mut Vec<Reference>) {
+    match stmt {
+        Stmt::Expression { expression } | Stmt::Print { expression, .. } => {
+            expr_references(expression, interpreter, out)
+        }
+        Stmt::Var { initializer, .. } => {
+            if let Some(initializer) = initializer {
+                expr_references(initializer, interpreter, out);
+            }
+        }
+        Stmt::Block { statements } | Stmt::Function { body: statements, .. } => {
+            for stmt in statements.iter() {
+                stmt_references(stmt, interpreter, out);
+            }
+        }
+        Stmt::If {
+            condition,
+            then,
+            else_branch,
+        } => {
+            expr_references(condition, interpreter, out);
+            stmt_references(then, interpreter, out);
+            if let Some(else_branch) = else_branch {
+                stmt_references(else_branch, interpreter, out);
+            }
+        }
+        Stmt::While { condition, body } => {
+            expr_references(condition, interpreter, out);
+            stmt_references(body, interpreter, out);
+        }
+        Stmt::Return { value, .. } => {
+            if let Some(value) = value {
+                expr_references(value, interpreter, out);
+            }
+        }
+        Stmt::Class {
+            methods,
+            superclass,
+            ..
+        } => {
+            if let Some(superclass) = superclass {
+                expr_references(superclass, interpreter, out);
+            }
+            for method in methods.iter() {
+                stmt_references(method, interpreter, out);
+            }
+        }
+    }
+}
+
+fn expr_references(expr: &Expr, interpreter: &Interpreter, out: &mut Vec<Reference>) {
+    let mut reference = |name: &Token| {
+        out.push(Reference {
+            name: name.clone(),
+            depth: interpreter.locals.get(expr).cloned(),
+        });
+    };
+    match expr {
+        Expr::Variable { name } => reference(name),
+        Expr::This { keyword } | Expr::Super { keyword, .. } => reference(keyword),
+        Expr::Assign { name, value } => {
+            reference(name);
+            expr_references(value, interpreter, out);
+        }
+        Expr::Literal { .. } => {}
+        Expr::Grouping { expression } => expr_references(expression, interpreter, out),
+        Expr::Unary { right, .. } => expr_references(right, interpreter, out),
+        Expr::Binary { left, right, .. } | Expr::Logical { left, right, .. } => {
+            expr_references(left, interpreter, out);
+            expr_references(right, interpreter, out);
+        }
+        Expr::Call {
+            callee, arguments, ..
+        } => {
+            expr_references(callee, interpreter, out);
+            for argument in arguments.iter() {
+                expr_references(argument, interpreter, out);
+            }
+        }
+        Expr::Get { object, .. } => expr_references(object, interpreter, out),
+        Expr::Set { object, value, .. } => {
+            expr_references(object, interpreter, out);
+            expr_references(value, interpreter, out);
+        }
+        Expr::Array { values } => {
+            for value in values.iter() {
+                expr_references(value, interpreter, out);
+            }
+        }
+        Expr::GetIndexed { object, index, .. } => {
+            expr_references(object, interpreter, out);
+            expr_references(index, interpreter, out);
+        }
+        Expr::SetIndexed {
+            object,
+            index,
+            value,
+            ..
+        } => {
+            expr_references(object, interpreter, out);
+            expr_references(index, interpreter, out);
+            expr_references(value, interpreter, out);
+        }
+    }
+}
+
+pub fn references_text(references: &[Reference]) -> String {
+    return references
+        .iter()
+        .map(|reference| {
+            let resolved = match reference.depth {
+                Some(depth) => format!("local, depth {}", depth),
+                None => String::from("global"),
+            };
+            return format!(
+                "{}:{}\t{} -> {}\n",
+                reference.name.line, reference.name.column, reference.name.lexeme, resolved
+            );
+        })
+        .collect();
+}
+
+pub fn references_json(references: &[Reference]) -> Value {
+    return Value::Array(
+        references
+            .iter()
+            .map(|reference| {
+                return json!({
+                    "name": reference.name.lexeme,
+                    "line": reference.name.line,
+                    "column": reference.name.column,
+                    "depth": reference.depth
+                });
+            })
+            .collect(),
+    );
+}
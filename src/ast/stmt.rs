@@ -8,6 +8,7 @@ pub enum Stmt {
         expression: Expr,
     },
     Print {
+        keyword: Token,
         expression: Expr,
     },
     Var {
@@ -29,6 +30,10 @@ pub enum Stmt {
     Function {
         name: Token,
         params: Vec<Token>,
+        /// Default value of each parameter, `None` for parameters that must be passed
+        defaults: Vec<Option<Expr>>,
+        /// The last parameter collects the remaining arguments into an array
+        rest: bool,
         body: Vec<Stmt>,
     },
     Return {
@@ -43,13 +48,31 @@ pub enum Stmt {
 }
 
 impl Stmt {
+    /// Finds the token that best represents the statement's location
+    pub fn token(&self) -> Option<&Token> {
+        match self {
+            Self::Expression { expression } => expression.token(),
+            Self::Print { keyword, .. } => Some(keyword),
+            Self::Var { name, .. } => Some(name),
+            Self::Block { statements } => statements.iter().find_map(|stmt| stmt.token()),
+            Self::If { condition, then, .. } => condition.token().or_else(|| then.token()),
+            Self::While { condition, body } => condition.token().or_else(|| body.token()),
+            Self::Function { name, .. } => Some(name),
+            Self::Return { keyword, .. } => Some(keyword),
+            Self::Class { name, .. } => Some(name),
+        }
+    }
+
     pub fn accept<V>(&mut self, visitor: &mut V) -> V::R
     where
         V: StmtVisitor,
     {
         match self {
             Self::Expression { expression } => visitor.visit_expression(expression),
-            Self::Print { expression } => visitor.visit_print(expression),
+            Self::Print {
+                keyword,
+                expression,
+            } => visitor.visit_print(keyword, expression),
             Self::Var { name, initializer } => visitor.visit_var(name, initializer),
             Self::Block { statements } => visitor.visit_block(statements),
             Self::If {
@@ -58,7 +81,13 @@ impl Stmt {
                 else_branch,
             } => visitor.visit_if(condition, then, else_branch),
             Self::While { condition, body } => visitor.visit_while(condition, body),
-            Self::Function { name, params, body } => visitor.visit_function(name, params, body),
+            Self::Function {
+                name,
+                params,
+                defaults,
+                rest,
+                body,
+            } => visitor.visit_function(name, params, defaults, *rest, body),
             Self::Return { keyword, value } => visitor.visit_return(keyword, value),
             Self::Class {
                 name,
@@ -5,7 +5,7 @@ use crate::token::Token;
 pub trait StmtVisitor {
     type R;
     fn visit_expression(&mut self, expression: &Expr) -> Self::R;
-    fn visit_print(&mut self, expression: &Expr) -> Self::R;
+    fn visit_print(&mut self, keyword: &Token, expression: &Expr) -> Self::R;
     fn visit_var(&mut self, name: &Token, initializer: &Option<Expr>) -> Self::R;
     fn visit_block(&mut self, statements: &mut Vec<Stmt>) -> Self::R;
     fn visit_if(
@@ -19,6 +19,8 @@ pub trait StmtVisitor {
         &mut self,
         name: &Token,
         params: &Vec<Token>,
+        defaults: &[Option<Expr>],
+        rest: bool,
         body: &mut Vec<Stmt>,
     ) -> Self::R;
     fn visit_return(&mut self, keyword: &Token, value: &Option<Expr>) -> Self::R;
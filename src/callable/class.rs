@@ -6,6 +6,7 @@ use crate::hashable::HashableRcRefCell;
 use crate::interpreter::Interpreter;
 use crate::literal::Literal;
 use crate::token::Token;
+use std::rc::Rc;
 
 use super::function::QalamFunction;
 
@@ -14,6 +15,9 @@ pub struct QalamClass {
     pub name: String,
     pub methods: HashableMap<String, Box<dyn QalamCallable>>,
     pub superclass: Option<Box<QalamClass>>,
+    /// Shared by the copies of a jamat, so jamats made by different runs of the same
+    /// declaration (e.g. inside a kaam called twice) aren't identical
+    identity: Rc<()>,
 }
 
 impl QalamClass {
@@ -26,6 +30,7 @@ impl QalamClass {
             name,
             methods,
             superclass,
+            identity: Rc::new(()),
         };
     }
 
@@ -54,9 +59,11 @@ impl QalamCallable for QalamClass {
         let initializer = self.find_method(&String::from("banao"));
         if let Some(initializer) = initializer {
             if let Some(initializer) = initializer.as_any().downcast_ref::<QalamFunction>() {
-                initializer
-                    .bind(instance.clone())
-                    .call(_interpreter, _arguments, _paren)?;
+                _interpreter.call(
+                    Box::new(initializer.bind(instance.clone())),
+                    _arguments,
+                    _paren,
+                )?;
             }
         }
         return Ok(Some(Literal::Instance(instance)));
@@ -72,6 +79,16 @@ impl QalamCallable for QalamClass {
         return 0;
     }
 
+    fn max_arity(&self) -> Option<usize> {
+        let initializer = self.find_method(&String::from("banao"));
+        if let Some(initializer) = initializer {
+            if let Some(initializer) = initializer.as_any().downcast_ref::<QalamFunction>() {
+                return initializer.max_arity();
+            }
+        }
+        return Some(0);
+    }
+
     fn clone_box(&self) -> Box<dyn QalamCallable> {
         return Box::new(self.clone());
     }
@@ -83,4 +100,15 @@ impl QalamCallable for QalamClass {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn name(&self) -> String {
+        return self.name.to_owned();
+    }
+
+    fn is_identical(&self, other: &dyn QalamCallable) -> bool {
+        if let Some(other) = other.as_any().downcast_ref::<QalamClass>() {
+            return Rc::ptr_eq(&self.identity, &other.identity);
+        }
+        return false;
+    }
 }
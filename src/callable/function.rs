@@ -5,7 +5,7 @@ use crate::environment::Environment;
 use crate::error::RuntimeError;
 use crate::hashable::HashableRcRefCell;
 use crate::interpreter::Interpreter;
-use crate::literal::Literal;
+use crate::literal::{Literal, QalamArray};
 use crate::token::Token;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -55,16 +55,37 @@ impl QalamCallable for QalamFunction {
             Stmt::Function {
                 name: _,
                 params,
+                defaults,
+                rest,
                 body,
             } => {
+                let mut arguments = arguments.into_iter();
                 for i in 0..params.len() {
                     let param_name = &params[i].lexeme;
-                    let arg = &arguments[i];
-                    env.borrow_mut().define(param_name.to_owned(), arg.clone());
+                    let arg = match (*rest && i == params.len() - 1, arguments.next()) {
+                        (true, first) => {
+                            let extra: Vec<Option<Literal>> = first.into_iter().chain(arguments.by_ref()).collect();
+                            interpreter.allocate_elements(extra.len(), paren)?;
+                            Some(Literal::Array(HashableRcRefCell::init(QalamArray::from_vec(
+                                extra,
+                            ))))
+                        }
+                        (false, Some(arg)) => arg,
+                        // Defaults are evaluated in the function's scope, so they can use earlier parameters
+                        (false, None) => match &defaults[i] {
+                            Some(default) => interpreter.evaluate_in(default, env.clone())?,
+                            None => None,
+                        },
+                    };
+                    env.borrow_mut().define(param_name.to_owned(), arg);
                 }
                 match interpreter.execute_block(body, env) {
                     Ok(_) => {}
-                    Err(e) => {
+                    Err(mut e) => {
+                        if e.tail_call.is_some() {
+                            // Made by the interpreter once this call has returned
+                            return Err(e);
+                        }
                         if e.message == String::from("dummy") {
                             if self.is_initializer {
                                 return Ok(Environment::get_at(
@@ -73,7 +94,7 @@ impl QalamCallable for QalamFunction {
                                     "yeh".to_string(),
                                 )?);
                             }
-                            return Ok(e.return_value);
+                            return Ok(e.return_value.take());
                         } else {
                             return Err(e);
                         }
@@ -99,15 +120,25 @@ impl QalamCallable for QalamFunction {
 
     fn arity(&self) -> usize {
         match &self.declaration {
-            Stmt::Function {
-                name: _,
-                params,
-                body: _,
-            } => params.len(),
+            Stmt::Function { defaults, rest, .. } => {
+                let required = defaults.iter().filter(|default| default.is_none()).count();
+                match rest {
+                    true => required - 1,
+                    false => required,
+                }
+            }
             _ => 0,
         }
     }
 
+    fn max_arity(&self) -> Option<usize> {
+        match &self.declaration {
+            Stmt::Function { rest: true, .. } => None,
+            Stmt::Function { params, .. } => Some(params.len()),
+            _ => Some(0),
+        }
+    }
+
     fn clone_box(&self) -> Box<dyn QalamCallable> {
         return Box::new(self.clone());
     }
@@ -115,16 +146,13 @@ impl QalamCallable for QalamFunction {
     fn to_string(&self) -> String {
         match &self.declaration {
             Stmt::Function {
-                name,
-                params,
-                body: _,
+                name, params, rest, ..
             } => {
-                let param_string = params
-                    .iter()
-                    .map(|t| &t.lexeme)
-                    .cloned()
-                    .collect::<Vec<String>>()
-                    .join(", ");
+                let mut params: Vec<String> = params.iter().map(|t| t.lexeme.to_owned()).collect();
+                if let (true, Some(last)) = (rest, params.last_mut()) {
+                    *last = format!("...{}", last);
+                }
+                let param_string = params.join(", ");
                 return format!("<kaam {}({})>", name.lexeme, param_string);
             }
             _ => {
@@ -136,4 +164,27 @@ impl QalamCallable for QalamFunction {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn name(&self) -> String {
+        match &self.declaration {
+            Stmt::Function { name, .. } => name.lexeme.to_owned(),
+            _ => String::from("khali"),
+        }
+    }
+
+    fn is_identical(&self, other: &dyn QalamCallable) -> bool {
+        // Same declaration closed over the same environment
+        if let Some(other) = other.as_any().downcast_ref::<QalamFunction>() {
+            if let (
+                Stmt::Function { name, .. },
+                Stmt::Function {
+                    name: other_name, ..
+                },
+            ) = (&self.declaration, &other.declaration)
+            {
+                return name == other_name && Rc::ptr_eq(&self.closure, &other.closure);
+            }
+        }
+        return false;
+    }
 }
@@ -9,6 +9,8 @@ use crate::token::Token;
 pub struct QalamInstance {
     pub class: QalamClass,
     pub fields: HashableMap<String, Option<Literal>>,
+    /// Made by the interpreter to group natives, like `math`, rather than from a jamat
+    pub namespace: bool,
 }
 
 impl QalamInstance {
@@ -16,9 +18,17 @@ impl QalamInstance {
         return Self {
             class,
             fields: HashableMap::new(),
+            namespace: false,
         };
     }
 
+    /// Creates a namespace, an instance whose fields are natives and constants
+    pub fn init_namespace(class: QalamClass) -> Self {
+        let mut namespace = Self::init(class);
+        namespace.namespace = true;
+        return namespace;
+    }
+
     pub fn to_string(&self) -> String {
         return format!("<instanceof {}>", self.class.name);
     }
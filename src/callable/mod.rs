@@ -20,6 +20,24 @@ pub trait QalamCallable: std::fmt::Debug + Any {
     fn to_string(&self) -> String;
     fn arity(&self) -> usize;
     fn as_any(&self) -> &dyn Any;
+
+    /// Most arguments the callable takes, `None` if it takes any number
+    /// `arity` is the fewest it takes
+    fn max_arity(&self) -> Option<usize> {
+        return Some(self.arity());
+    }
+
+    /// Bare name of the callable (e.g. `read_file`), used in call stack frames
+    fn name(&self) -> String;
+
+    /// Checks if two callables are the same value (used for `==`)
+    /// By default callables of the same type are identical, which holds for natives without
+    /// fields. Callables with state (e.g. the name of a math function, or the declaration of
+    /// a kaam) override it to compare that too
+    /// Identical callables must have the same type and `name`, since those are what they hash by
+    fn is_identical(&self, other: &dyn QalamCallable) -> bool {
+        return self.as_any().type_id() == other.as_any().type_id();
+    }
 }
 
 impl Clone for Box<dyn QalamCallable> {
@@ -30,17 +48,15 @@ impl Clone for Box<dyn QalamCallable> {
 
 impl Hash for Box<dyn QalamCallable> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.to_string().hash(state)
+        // Consistent with `is_identical`, which only holds for callables of the same type and name
+        self.as_any().type_id().hash(state);
+        self.name().hash(state);
     }
 }
 
 impl PartialEq for Box<dyn QalamCallable> {
     fn eq(&self, other: &Self) -> bool {
-        self.clone().to_string() == other.clone().to_string()
-    }
-
-    fn ne(&self, other: &Self) -> bool {
-        return !self.eq(other);
+        return self.is_identical(other.as_ref());
     }
 }
 
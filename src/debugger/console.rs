@@ -0,0 +1,196 @@
+use super::{evaluate, scopes, variables, Frontend, Resume, StopReason};
+use crate::interpreter::Interpreter;
+use crate::literal::Literal;
+use rustyline::error::ReadlineError;
+use rustyline::Editor;
+use std::collections::BTreeSet;
+
+const HELP: &str = "\
+Commands:
+  c, continue        Run until the next breakpoint
+  s, step            Run the next statement, stepping into calls
+  n, next            Run the next statement, stepping over calls
+  o, out             Run until the current kaam returns
+  b, break <line>    Set a breakpoint
+  d, delete <line>   Remove a breakpoint
+  breakpoints        List breakpoints
+  bt, backtrace      Print the call stack
+  v, vars            Print the variables in scope
+  p, print <expr>    Evaluate an expression
+  l, list            Show the source around the current line
+  q, quit            Stop the program
+  h, help            Show this message
+An empty line repeats the last step command.";
+
+/// Number of lines shown on each side of the current line by `list`
+const LIST_CONTEXT: usize = 3;
+
+/// Debugger frontend that reads commands from the terminal
+pub struct Console {
+    lines: Vec<String>,
+    editor: Editor<(), rustyline::history::FileHistory>,
+    last_command: Option<Resume>,
+}
+
+impl Console {
+    pub fn init(source: &str) -> Self {
+        return Self {
+            lines: source.lines().map(|line| line.to_string()).collect(),
+            editor: Editor::<(), rustyline::history::FileHistory>::new()
+                .expect("Failed to initialize input reader."),
+            last_command: None,
+        };
+    }
+
+    fn source_line(&self, line: i64) -> String {
+        let text = self
+            .lines
+            .get((line - 1).max(0) as usize)
+            .map(|text| text.as_str())
+            .unwrap_or("");
+        return format!("{:>4} | {}", line, text);
+    }
+
+    fn list(&self, line: i64) {
+        let line = line.max(1) as usize;
+        let start = line.saturating_sub(LIST_CONTEXT).max(1);
+        let end = (line + LIST_CONTEXT).min(self.lines.len());
+        for i in start..=end {
+            let marker = if i == line { "->" } else { "  " };
+            println!("{} {}", marker, self.source_line(i as i64));
+        }
+    }
+
+    fn backtrace(interpreter: &Interpreter) {
+        for (i, (name, line)) in interpreter.call_stack().iter().enumerate() {
+            println!("#{} {} at line {}", i, name, line);
+        }
+    }
+
+    fn variables(interpreter: &Interpreter) {
+        for (name, environment) in scopes(interpreter, interpreter.environment.clone()) {
+            let variables = variables(&environment);
+            if variables.is_empty() {
+                continue;
+            }
+            println!("{}:", name);
+            for (name, value) in variables {
+                println!("  {} = {}", name, Literal::option_string(value));
+            }
+        }
+    }
+
+    fn parse_line(argument: &str) -> Option<i64> {
+        return argument.trim().parse::<i64>().ok().filter(|line| *line > 0);
+    }
+}
+
+impl Frontend for Console {
+    fn pause(
+        &mut self,
+        interpreter: &mut Interpreter,
+        breakpoints: &mut BTreeSet<i64>,
+        line: i64,
+        reason: StopReason,
+    ) -> Resume {
+        match reason {
+            StopReason::Breakpoint => println!("Breakpoint at line {}", line),
+            StopReason::Entry | StopReason::Step => {}
+        }
+        println!("{}", self.source_line(line));
+
+        loop {
+            let input = match self.editor.readline("(qalam) ") {
+                Ok(input) => input,
+                Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => {
+                    std::process::exit(0);
+                }
+                Err(err) => {
+                    eprintln!("Error reading input: {}", err);
+                    std::process::exit(1);
+                }
+            };
+            let input = input.trim();
+            if !input.is_empty() {
+                let _ = self.editor.add_history_entry(input);
+            }
+            let (command, argument) = match input.split_once(' ') {
+                Some((command, argument)) => (command, argument.trim()),
+                None => (input, ""),
+            };
+
+            let resume = match command {
+                "" => match self.last_command {
+                    Some(resume) => Some(resume),
+                    None => continue,
+                },
+                "c" | "continue" => Some(Resume::Continue),
+                "s" | "step" => Some(Resume::StepIn),
+                "n" | "next" => Some(Resume::StepOver),
+                "o" | "out" => Some(Resume::StepOut),
+                "b" | "break" => {
+                    match Self::parse_line(argument) {
+                        Some(line) => {
+                            breakpoints.insert(line);
+                            println!("Breakpoint set at line {}", line);
+                        }
+                        None => println!("Expected a line number, e.g. 'break 12'."),
+                    }
+                    None
+                }
+                "d" | "delete" => {
+                    match Self::parse_line(argument) {
+                        Some(line) if breakpoints.remove(&line) => {
+                            println!("Breakpoint removed from line {}", line)
+                        }
+                        Some(line) => println!("No breakpoint at line {}", line),
+                        None => println!("Expected a line number, e.g. 'delete 12'."),
+                    }
+                    None
+                }
+                "breakpoints" => {
+                    if breakpoints.is_empty() {
+                        println!("No breakpoints");
+                    }
+                    for line in breakpoints.iter() {
+                        println!("{}", self.source_line(*line));
+                    }
+                    None
+                }
+                "bt" | "backtrace" => {
+                    Self::backtrace(interpreter);
+                    None
+                }
+                "v" | "vars" => {
+                    Self::variables(interpreter);
+                    None
+                }
+                "p" | "print" => {
+                    match evaluate(interpreter, argument) {
+                        Ok(value) => println!("{}", Literal::option_string(value)),
+                        Err(message) => println!("Error: {}", message),
+                    }
+                    None
+                }
+                "l" | "list" => {
+                    self.list(line);
+                    None
+                }
+                "q" | "quit" => std::process::exit(0),
+                "h" | "help" => {
+                    println!("{}", HELP);
+                    None
+                }
+                _ => {
+                    println!("Unknown command '{}'. Type 'help' for a list of commands.", command);
+                    None
+                }
+            };
+
+            if let Some(resume) = resume {
+                self.last_command = Some(resume);
+                return resume;
+            }
+        }
+    }
+}
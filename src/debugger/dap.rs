@@ -0,0 +1,450 @@
+use super::{evaluate, scopes, variables, Debugger, Frontend, Resume, StopReason};
+use crate::environment::Environment;
+use crate::error::{ErrorReporter, ErrorType};
+use crate::framing::{read_framed_message, write_framed_message};
+use crate::hashable::HashableRcRefCell;
+use crate::interpreter::Interpreter;
+use crate::literal::{Literal, QalamArray};
+use crate::callable::instance::QalamInstance;
+use crate::parser::Parser;
+use crate::resolver::Resolver;
+use crate::scanner::Scanner;
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::io::{BufRead, BufReader, Write};
+use std::rc::Rc;
+
+/// The only thread of a qalam program
+const THREAD_ID: i64 = 1;
+
+/// Reads and writes Debug Adapter Protocol messages
+pub struct Connection {
+    input: Box<dyn BufRead>,
+    output: Box<dyn Write>,
+    seq: i64,
+}
+
+impl Connection {
+    pub fn init(input: Box<dyn BufRead>, output: Box<dyn Write>) -> Self {
+        return Self {
+            input,
+            output,
+            seq: 0,
+        };
+    }
+
+    /// Reads the next message, or `None` once the client closes the input
+    pub fn read(&mut self) -> Result<Option<Value>> {
+        return read_framed_message(&mut self.input);
+    }
+
+    fn send(&mut self, mut message: Value) {
+        self.seq += 1;
+        message["seq"] = json!(self.seq);
+        let _ = write_framed_message(&mut self.output, &message);
+    }
+
+    pub fn respond(&mut self, request: &Value, body: Value) {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "success": true,
+            "command": request["command"],
+            "body": body
+        }));
+    }
+
+    pub fn respond_error(&mut self, request: &Value, message: &str) {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "success": false,
+            "command": request["command"],
+            "message": message
+        }));
+    }
+
+    pub fn event(&mut self, event: &str, body: Value) {
+        self.send(json!({ "type": "event", "event": event, "body": body }));
+    }
+
+    fn output(&mut self, category: &str, text: &str) {
+        self.event("output", json!({ "category": category, "output": text }));
+    }
+}
+
+/// Sends what the program prints to the client as `output` events, one per line
+/// The rest is sent when the output is flushed, which happens when the program stops or pauses
+struct OutputEvents {
+    connection: Rc<RefCell<Connection>>,
+    buffer: String,
+}
+
+impl Write for OutputEvents {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.push_str(&String::from_utf8_lossy(buf));
+        while let Some(end) = self.buffer.find('\n') {
+            let line: String = self.buffer.drain(..=end).collect();
+            self.connection.borrow_mut().output("stdout", &line);
+        }
+        return Ok(buf.len());
+    }
+
+    /// Sends the start of a line that hasn't ended yet, e.g. a prompt
+    fn flush(&mut self) -> std::io::Result<()> {
+        if !self.buffer.is_empty() {
+            let text = std::mem::take(&mut self.buffer);
+            self.connection.borrow_mut().output("stdout", &text);
+        }
+        return Ok(());
+    }
+}
+
+/// A value whose variables can be expanded by the client
+enum Container {
+    Environment(Rc<RefCell<Environment>>),
+    Array(HashableRcRefCell<QalamArray>),
+    Instance(HashableRcRefCell<QalamInstance>),
+}
+
+/// Debugger frontend driven by an editor over the Debug Adapter Protocol
+pub struct DapFrontend {
+    connection: Rc<RefCell<Connection>>,
+    path: String,
+    /// Containers handed out while paused, `variablesReference` is the index plus 1
+    containers: Vec<Container>,
+}
+
+impl DapFrontend {
+    fn reference(&mut self, value: &Option<Literal>) -> usize {
+        let container = match value {
+            Some(Literal::Array(array)) => Container::Array(array.clone()),
+            Some(Literal::Instance(instance)) => Container::Instance(instance.clone()),
+            _ => return 0,
+        };
+        self.containers.push(container);
+        return self.containers.len();
+    }
+
+    fn variable(&mut self, name: String, value: Option<Literal>) -> Value {
+        let reference = self.reference(&value);
+        return json!({
+            "name": name,
+            "value": Literal::option_string(value),
+            "variablesReference": reference
+        });
+    }
+
+    fn variables(&mut self, reference: usize) -> Vec<Value> {
+        let values: Vec<(String, Option<Literal>)> = match self.containers.get(reference - 1) {
+            Some(Container::Environment(environment)) => variables(environment),
+            Some(Container::Array(array)) => array
+                .0
+                .borrow()
+                .elements
+                .iter()
+                .enumerate()
+                .map(|(i, value)| (format!("[{}]", i), value.clone()))
+                .collect(),
+            Some(Container::Instance(instance)) => {
+                let mut fields: Vec<(String, Option<Literal>)> = instance
+                    .0
+                    .borrow()
+                    .fields
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.clone()))
+                    .collect();
+                fields.sort_by(|a, b| a.0.cmp(&b.0));
+                fields
+            }
+            None => Vec::new(),
+        };
+        return values
+            .into_iter()
+            .map(|(name, value)| self.variable(name, value))
+            .collect();
+    }
+
+    fn stack_trace(&self, interpreter: &Interpreter) -> Value {
+        let frames: Vec<Value> = interpreter
+            .call_stack()
+            .iter()
+            .enumerate()
+            .map(|(i, (name, line))| {
+                return json!({
+                    "id": i,
+                    "name": name,
+                    "line": line,
+                    "column": 1,
+                    "source": { "path": self.path }
+                });
+            })
+            .collect();
+        return json!({ "stackFrames": frames, "totalFrames": frames.len() });
+    }
+
+    fn scopes(&mut self, interpreter: &Interpreter, frame: i64) -> Value {
+        // Frames are numbered like `stack_trace`, innermost first
+        let environment = usize::try_from(frame)
+            .ok()
+            .and_then(|frame| interpreter.frame_environments().get(frame).cloned())
+            .unwrap_or_else(|| interpreter.globals.clone());
+        let scopes = scopes(interpreter, environment);
+        let scopes: Vec<Value> = scopes
+            .into_iter()
+            .map(|(name, environment)| {
+                self.containers.push(Container::Environment(environment));
+                return json!({
+                    "name": name,
+                    "variablesReference": self.containers.len(),
+                    "expensive": false
+                });
+            })
+            .collect();
+        return json!({ "scopes": scopes });
+    }
+}
+
+fn set_breakpoints(request: &Value, breakpoints: &mut BTreeSet<i64>) -> Value {
+    breakpoints.clear();
+    let lines: Vec<i64> = request["arguments"]["breakpoints"]
+        .as_array()
+        .map(|list| list.iter().filter_map(|b| b["line"].as_i64()).collect())
+        .unwrap_or_default();
+    breakpoints.extend(lines.iter());
+    let verified: Vec<Value> = lines
+        .iter()
+        .map(|line| json!({ "verified": true, "line": line }))
+        .collect();
+    return json!({ "breakpoints": verified });
+}
+
+impl Frontend for DapFrontend {
+    fn pause(
+        &mut self,
+        interpreter: &mut Interpreter,
+        breakpoints: &mut BTreeSet<i64>,
+        _line: i64,
+        reason: StopReason,
+    ) -> Resume {
+        let _ = interpreter.output.flush();
+        self.connection.borrow_mut().event(
+            "stopped",
+            json!({
+                "reason": reason.name(),
+                "threadId": THREAD_ID,
+                "allThreadsStopped": true
+            }),
+        );
+
+        loop {
+            let request = match self.connection.borrow_mut().read() {
+                Ok(Some(request)) => request,
+                _ => std::process::exit(0),
+            };
+            let arguments = &request["arguments"];
+            let (body, resume) = match request["command"].as_str().unwrap_or("") {
+                "threads" => (json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] }), None),
+                "stackTrace" => (self.stack_trace(interpreter), None),
+                "scopes" => {
+                    let frame = arguments["frameId"].as_i64().unwrap_or(0);
+                    (self.scopes(interpreter, frame), None)
+                }
+                "variables" => {
+                    let reference = arguments["variablesReference"].as_u64().unwrap_or(0);
+                    let variables = self.variables(reference as usize);
+                    (json!({ "variables": variables }), None)
+                }
+                "evaluate" => {
+                    let expression = arguments["expression"].as_str().unwrap_or("");
+                    match evaluate(interpreter, expression) {
+                        Ok(value) => {
+                            let reference = self.reference(&value);
+                            let result = Literal::option_string(value);
+                            (json!({ "result": result, "variablesReference": reference }), None)
+                        }
+                        Err(message) => {
+                            self.connection.borrow_mut().respond_error(&request, &message);
+                            continue;
+                        }
+                    }
+                }
+                "setBreakpoints" => (set_breakpoints(&request, breakpoints), None),
+                "continue" => (json!({ "allThreadsContinued": true }), Some(Resume::Continue)),
+                "next" => (json!({}), Some(Resume::StepOver)),
+                "stepIn" => (json!({}), Some(Resume::StepIn)),
+                "stepOut" => (json!({}), Some(Resume::StepOut)),
+                "disconnect" | "terminate" => {
+                    self.connection.borrow_mut().respond(&request, json!({}));
+                    std::process::exit(0);
+                }
+                command => {
+                    let message = format!("Request '{}' is not supported.", command);
+                    self.connection.borrow_mut().respond_error(&request, &message);
+                    continue;
+                }
+            };
+            self.connection.borrow_mut().respond(&request, body);
+
+            if let Some(resume) = resume {
+                self.containers.clear();
+                return resume;
+            }
+        }
+    }
+}
+
+/// Runs a debugging session over stdio
+///
+/// The client configures the session with `launch`, `setBreakpoints` and
+/// `configurationDone`, after which the program in `launch`'s `program` argument runs
+/// in `interpreter`, which has the limits, permissions and seed of the session.
+///
+/// ### Returns
+/// `Result<i32>` - the exit code of the program
+pub fn run(interpreter: Rc<RefCell<Interpreter>>) -> Result<i32> {
+    let connection = Rc::new(RefCell::new(Connection::init(
+        Box::new(BufReader::new(std::io::stdin())),
+        Box::new(std::io::stdout()),
+    )));
+    let mut breakpoints = BTreeSet::new();
+    let mut program = None;
+    let mut stop_on_entry = false;
+
+    loop {
+        let Some(request) = connection.borrow_mut().read()? else {
+            return Ok(0);
+        };
+        let mut connection = connection.borrow_mut();
+        match request["command"].as_str().unwrap_or("") {
+            "initialize" => {
+                connection.respond(
+                    &request,
+                    json!({
+                        "supportsConfigurationDoneRequest": true,
+                        "supportsEvaluateForHovers": true
+                    }),
+                );
+                connection.event("initialized", json!({}));
+            }
+            "launch" => {
+                program = request["arguments"]["program"].as_str().map(String::from);
+                stop_on_entry = request["arguments"]["stopOnEntry"].as_bool().unwrap_or(false);
+                connection.respond(&request, json!({}));
+            }
+            "setBreakpoints" => {
+                let body = set_breakpoints(&request, &mut breakpoints);
+                connection.respond(&request, body);
+            }
+            "setExceptionBreakpoints" => connection.respond(&request, json!({})),
+            "threads" => connection.respond(
+                &request,
+                json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] }),
+            ),
+            "configurationDone" => {
+                connection.respond(&request, json!({}));
+                break;
+            }
+            "disconnect" | "terminate" => {
+                connection.respond(&request, json!({}));
+                return Ok(0);
+            }
+            command => {
+                let message = format!("Request '{}' is not supported.", command);
+                connection.respond_error(&request, &message);
+            }
+        }
+    }
+
+    // Colours would show up as escape codes in the client
+    colored::control::set_override(false);
+    let exit_code = match program {
+        Some(path) => debug_program(&path, interpreter, connection.clone(), breakpoints, stop_on_entry),
+        None => {
+            connection
+                .borrow_mut()
+                .output("stderr", "No program was given in the launch request.\n");
+            1
+        }
+    };
+
+    let mut connection = connection.borrow_mut();
+    connection.event("terminated", json!({}));
+    connection.event("exited", json!({ "exitCode": exit_code }));
+    while let Some(request) = connection.read()? {
+        connection.respond(&request, json!({}));
+        if request["command"] == "disconnect" {
+            break;
+        }
+    }
+    return Ok(exit_code);
+}
+
+fn debug_program(
+    path: &str,
+    interpreter: Rc<RefCell<Interpreter>>,
+    connection: Rc<RefCell<Connection>>,
+    breakpoints: BTreeSet<i64>,
+    stop_on_entry: bool,
+) -> i32 {
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            let message = format!("Cannot read file {}: {}\n", path, e);
+            connection.borrow_mut().output("stderr", &message);
+            return 1;
+        }
+    };
+
+    let mut reporter = ErrorReporter::init_quiet();
+    let mut scanner = Scanner::init(&source, &mut reporter);
+    let tokens = scanner.scan_tokens().clone();
+    let statements = match Parser::init(&tokens).parse() {
+        Ok(mut statements) if reporter.errors.is_empty() => {
+            let mut resolver = Resolver::init(interpreter.clone());
+            if let Err(e) = resolver.resolve_stmts(&mut statements) {
+                reporter.runtime_error(&e.token, &e.message, ErrorType::Resolution);
+            }
+            statements
+        }
+        Ok(statements) => statements,
+        Err(e) => {
+            reporter.error_token(&e.token, &e.message, ErrorType::Syntax);
+            Vec::new()
+        }
+    };
+    if let Some(error) = reporter.errors.first() {
+        let message = format!("Error at line {}: {}\n", error.line, error.message);
+        connection.borrow_mut().output("stderr", &message);
+        return 65;
+    }
+
+    let frontend = DapFrontend {
+        connection: connection.clone(),
+        path: path.to_string(),
+        containers: Vec::new(),
+    };
+    let mut interpreter = interpreter.borrow_mut();
+    interpreter.output = Box::new(OutputEvents {
+        connection: connection.clone(),
+        buffer: String::new(),
+    });
+    interpreter.debugger = Some(Debugger::init(Box::new(frontend), breakpoints, stop_on_entry));
+    let result = interpreter.interpret(statements);
+    let _ = interpreter.output.flush();
+    if let Err(e) = result {
+        if let Some(code) = e.exit_code {
+            return code;
+        }
+        let err_type = match e.limit {
+            Some(_) => ErrorType::Limit,
+            None => ErrorType::Runtime,
+        };
+        let message = format!("{}: {}\n\t at line {}\n", err_type.to_string(), e.message, e.token.line);
+        connection.borrow_mut().output("stderr", &message);
+        return 75;
+    }
+    return 0;
+}
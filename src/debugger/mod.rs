@@ -0,0 +1,226 @@
+pub mod console;
+pub mod dap;
+use crate::environment::Environment;
+use crate::error::{ErrorReporter, ErrorType};
+use crate::interpreter::Interpreter;
+use crate::literal::Literal;
+use crate::parser::Parser;
+use crate::scanner::Scanner;
+use crate::ast::stmt::Stmt;
+use crate::token::Token;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopReason {
+    Entry,
+    Breakpoint,
+    Step,
+}
+
+impl StopReason {
+    /// Name of the reason in the Debug Adapter Protocol
+    pub fn name(&self) -> &'static str {
+        return match self {
+            StopReason::Entry => "entry",
+            StopReason::Breakpoint => "breakpoint",
+            StopReason::Step => "step",
+        };
+    }
+}
+
+/// How the program continues after a pause
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resume {
+    Continue,
+    StepIn,
+    StepOver,
+    StepOut,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StepMode {
+    Continue,
+    StepIn,
+    /// Pause at the next statement at or above this call depth
+    StepOver(usize),
+    /// Pause at the next statement above this call depth
+    StepOut(usize),
+}
+
+/// The user interface of a debugging session
+pub trait Frontend {
+    /// Called when the program pauses before a statement
+    ///
+    /// ### Arguments
+    /// `interpreter` - the paused interpreter, for inspecting the call stack and variables
+    /// `breakpoints` - lines with breakpoints, which can be changed while paused
+    /// `line` - line of the statement about to run
+    /// `reason` - why the program paused
+    /// ### Returns
+    /// `Resume` - how to continue running the program
+    fn pause(
+        &mut self,
+        interpreter: &mut Interpreter,
+        breakpoints: &mut BTreeSet<i64>,
+        line: i64,
+        reason: StopReason,
+    ) -> Resume;
+}
+
+/// Pauses the interpreter before statements for breakpoints and stepping
+///
+/// Statements that start on the same line after the previous one (e.g. the body of a
+/// one line `agar`) belong to the same step. Running the same line again, for example
+/// in the next iteration of a loop, is a new step.
+pub struct Debugger {
+    pub breakpoints: BTreeSet<i64>,
+    mode: StepMode,
+    entered: bool,
+    /// Line, column and call depth of the previous statement
+    last: Option<(i64, i64, usize)>,
+    frontend: Box<dyn Frontend>,
+}
+
+impl Debugger {
+    pub fn init(frontend: Box<dyn Frontend>, breakpoints: BTreeSet<i64>, stop_on_entry: bool) -> Self {
+        return Self {
+            breakpoints,
+            mode: match stop_on_entry {
+                true => StepMode::StepIn,
+                false => StepMode::Continue,
+            },
+            entered: false,
+            last: None,
+            frontend,
+        };
+    }
+
+    /// Called by the interpreter before it executes a statement
+    pub fn before_statement(&mut self, interpreter: &mut Interpreter, token: Option<&Token>) {
+        let depth = interpreter.frames.len();
+        let (line, column) = match token {
+            Some(token) => (token.line, token.column),
+            None => (interpreter.current_line(), 0),
+        };
+        let new_step = match self.last {
+            Some((last_line, last_column, last_depth)) => {
+                line != last_line || column <= last_column || depth != last_depth
+            }
+            None => true,
+        };
+        self.last = Some((line, column, depth));
+        if !new_step {
+            return;
+        }
+
+        let reason = if !self.entered && self.mode == StepMode::StepIn {
+            Some(StopReason::Entry)
+        } else if self.breakpoints.contains(&line) {
+            Some(StopReason::Breakpoint)
+        } else {
+            match self.mode {
+                StepMode::StepIn => Some(StopReason::Step),
+                StepMode::StepOver(d) if depth <= d => Some(StopReason::Step),
+                StepMode::StepOut(d) if depth < d => Some(StopReason::Step),
+                _ => None,
+            }
+        };
+        self.entered = true;
+
+        if let Some(reason) = reason {
+            let resume = self
+                .frontend
+                .pause(interpreter, &mut self.breakpoints, line, reason);
+            self.mode = match resume {
+                Resume::Continue => StepMode::Continue,
+                Resume::StepIn => StepMode::StepIn,
+                Resume::StepOver => StepMode::StepOver(depth),
+                Resume::StepOut => StepMode::StepOut(depth),
+            };
+        }
+    }
+}
+
+/// Parses and evaluates an expression in the paused interpreter's current environment
+///
+/// ### Returns
+/// `Result<Option<Literal>, String>` - the value or an error message
+pub fn evaluate(interpreter: &mut Interpreter, source: &str) -> Result<Option<Literal>, String> {
+    let mut source = source.trim().to_string();
+    if !source.ends_with(';') {
+        source.push(';');
+    }
+
+    let mut reporter = ErrorReporter::init_quiet();
+    let mut scanner = Scanner::init(&source, &mut reporter);
+    // Line 0 keeps the expression from matching resolved expressions of the program
+    let tokens: Vec<Token> = scanner
+        .scan_tokens()
+        .iter()
+        .map(|token| {
+            let mut token = token.clone();
+            token.line = 0;
+            return token;
+        })
+        .collect();
+    if let Some(error) = reporter.errors.first() {
+        return Err(error.message.to_string());
+    }
+
+    let statements = match Parser::init(&tokens).parse() {
+        Ok(statements) => statements,
+        Err(e) => {
+            reporter.error_token(&e.token, &e.message, ErrorType::Syntax);
+            return Err(reporter.errors[0].message.to_string());
+        }
+    };
+    return match statements.as_slice() {
+        [Stmt::Expression { expression }] => interpreter
+            .evaluate_dynamic(expression)
+            .map_err(|e| e.message.clone()),
+        _ => Err(String::from("Expected a single expression.")),
+    };
+}
+
+/// Lists the environments visible from `environment`, innermost first, e.g. from the
+/// current environment or one of `Interpreter::frame_environments`
+///
+/// ### Returns
+/// `Vec<(String, Rc<RefCell<Environment>>)>` - the name and environment of each scope
+pub fn scopes(
+    interpreter: &Interpreter,
+    environment: Rc<RefCell<Environment>>,
+) -> Vec<(String, Rc<RefCell<Environment>>)> {
+    let mut scopes = Vec::new();
+    let mut environment = Some(environment);
+    while let Some(env) = environment {
+        let name = if Rc::ptr_eq(&env, &interpreter.globals) {
+            String::from("Globals")
+        } else if scopes.is_empty() {
+            String::from("Locals")
+        } else {
+            format!("Enclosing {}", scopes.len())
+        };
+        environment = env.borrow().enclosing.clone();
+        scopes.push((name, env));
+    }
+    return scopes;
+}
+
+/// Lists the variables defined in an environment, sorted by name
+/// Native functions are left out since every program has them
+pub fn variables(environment: &Rc<RefCell<Environment>>) -> Vec<(String, Option<Literal>)> {
+    let mut variables: Vec<(String, Option<Literal>)> = environment
+        .borrow()
+        .iter()
+        .filter(|(_, value)| match value {
+            Some(Literal::Callable(callable)) => !callable.to_string().starts_with("<native"),
+            _ => true,
+        })
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect();
+    variables.sort_by(|a, b| a.0.cmp(&b.0));
+    return variables;
+}
@@ -23,6 +23,11 @@ impl Environment {
         self.values.insert(name, value);
     }
 
+    /// Iterates over the values defined directly in this environment
+    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, String, Option<Literal>> {
+        return self.values.iter();
+    }
+
     pub fn assign(&mut self, name: &Token, value: Option<Literal>) -> Result<(), RuntimeError> {
         if self.values.contains_key(&name.lexeme) {
             self.values.insert(name.lexeme.to_owned(), value);
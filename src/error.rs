@@ -1,12 +1,16 @@
+use crate::callable::QalamCallable;
+use crate::limits::LimitKind;
 use crate::literal::Literal;
 use crate::token::{Token, TokenType};
 use std::fmt;
+use std::io::Write;
 
 pub enum ErrorType {
     Error,
     Syntax,
     Runtime,
     Resolution,
+    Limit,
 }
 
 impl ErrorType {
@@ -16,13 +20,25 @@ impl ErrorType {
             Self::Syntax => "SyntaxError",
             Self::Runtime => "RuntimeError",
             Self::Resolution => "ResolutionError",
+            Self::Limit => "LimitError",
         }
     }
 }
 
+/// An error recorded by a quiet `ErrorReporter`
+#[derive(Debug, Clone)]
+pub struct ReportedError {
+    pub line: i64,
+    pub message: String,
+}
+
 pub struct ErrorReporter {
     pub had_error: bool,
     pub had_runtime_error: bool,
+    pub quiet: bool,
+    pub errors: Vec<ReportedError>,
+    /// Where errors are printed, stderr when `None`
+    pub output: Option<Box<dyn Write>>,
 }
 
 impl ErrorReporter {
@@ -30,9 +46,19 @@ impl ErrorReporter {
         return Self {
             had_error: false,
             had_runtime_error: false,
+            quiet: false,
+            errors: Vec::new(),
+            output: None,
         };
     }
 
+    /// Creates a reporter that records errors in `errors` instead of printing them
+    pub fn init_quiet() -> Self {
+        let mut reporter = Self::init();
+        reporter.quiet = true;
+        return reporter;
+    }
+
     pub fn error_token(&mut self, token: &Token, message: &str, err_type: ErrorType) {
         match token.token_type {
             TokenType::Eof => self.report(token.line, message, Some("at end"), err_type),
@@ -50,22 +76,94 @@ impl ErrorReporter {
     }
 
     pub fn runtime_error(&mut self, token: &Token, message: &str, err_type: ErrorType) {
-        eprintln!("{}: {}", err_type.to_string(), message);
-        eprintln!("\t at line {}", token.line);
+        if self.quiet {
+            self.record(token.line, message);
+            self.had_runtime_error = true;
+            return;
+        }
+        self.print(format!("{}: {}", err_type.to_string(), message));
+        self.print(format!("\t at line {}", token.line));
         self.had_runtime_error = true;
     }
 
+    /// Reports a runtime error along with the calls that led to it, innermost last
+    pub fn runtime_error_trace(
+        &mut self,
+        token: &Token,
+        message: &str,
+        trace: &[CallFrame],
+        err_type: ErrorType,
+    ) {
+        self.runtime_error(token, message, err_type);
+        if trace.is_empty() || self.quiet {
+            return;
+        }
+
+        // Each frame is executing at the call site of the frame after it
+        let mut lines = Vec::new();
+        lines.push((String::from("<script>"), trace[0].call_site.line, 0));
+        for (i, frame) in trace.iter().enumerate() {
+            let line = match trace.get(i + 1) {
+                Some(next) => next.call_site.line,
+                None => token.line,
+            };
+            lines.push((frame.name.to_owned(), line, frame.tail_calls));
+        }
+
+        self.print(String::from("Traceback (innermost last):"));
+        let mut i = 0;
+        while i < lines.len() {
+            let (name, line, tail_calls) = &lines[i];
+            if *tail_calls > 0 {
+                let frames = if *tail_calls == 1 { "frame" } else { "frames" };
+                self.print(format!("\t ... {} {} replaced by tail calls", tail_calls, frames));
+            }
+            self.print(format!("\t {} at line {}", name, line));
+            // Collapse repeated frames from recursion
+            let mut repeated = 0;
+            while i + 1 + repeated < lines.len() && lines[i + 1 + repeated] == lines[i] {
+                repeated += 1;
+            }
+            if repeated > 0 {
+                let times = if repeated == 1 { "time" } else { "times" };
+                self.print(format!("\t ... repeated {} more {}", repeated, times));
+            }
+            i += 1 + repeated;
+        }
+    }
+
     pub fn report(&mut self, line: i64, message: &str, loc: Option<&str>, err_type: ErrorType) {
-        eprintln!("{}: {}", err_type.to_string(), message);
-        eprintln!("\t at line {}", line);
+        if self.quiet {
+            self.record(line, message);
+            self.had_error = true;
+            return;
+        }
+        self.print(format!("{}: {}", err_type.to_string(), message));
+        self.print(format!("\t at line {}", line));
         match loc {
             Some(val) => {
-                eprintln!("\t {}", val)
+                self.print(format!("\t {}", val))
             }
             None => {}
         };
         self.had_error = true;
     }
+
+    fn print(&mut self, line: String) {
+        match self.output.as_mut() {
+            Some(output) => {
+                let _ = writeln!(output, "{}", line);
+            }
+            None => eprintln!("{}", line),
+        }
+    }
+
+    fn record(&mut self, line: i64, message: &str) {
+        self.errors.push(ReportedError {
+            line,
+            message: message.to_string(),
+        });
+    }
 }
 
 #[derive(Debug)]
@@ -88,11 +186,67 @@ impl ParseError {
     }
 }
 
+/// A call that was in progress, used to build tracebacks
+#[derive(Debug, Clone)]
+pub struct CallFrame {
+    pub name: String,
+    pub call_site: Token,
+    /// Number of frames this one replaced by calls in tail position, which are left out of tracebacks
+    pub tail_calls: usize,
+}
+
+/// An argument of a native that has the wrong type or value
+/// Kept apart from the message so that methods can leave their receiver out of the numbering
+#[derive(Debug, Clone)]
+pub struct ArgumentError {
+    /// Position of the argument starting at 1, or `None` for the receiver of a method
+    pub position: Option<usize>,
+    pub param: String,
+    /// The argument as it is printed
+    pub value: String,
+    /// What the argument must be, e.g. "a number"
+    pub expected: String,
+}
+
+impl ArgumentError {
+    pub fn message(&self) -> String {
+        let position = match self.position {
+            Some(position) => format!("argument {}", position),
+            None => String::from("receiver"),
+        };
+        return format!(
+            "'{}' ({}) = {} must be {}!",
+            self.param, position, self.value, self.expected
+        );
+    }
+}
+
+/// A call in tail position (`wapis f(...)`) that is made by the caller's caller
+/// so that the call doesn't grow the stack
 #[derive(Debug)]
-pub struct RuntimeError {
+pub struct TailCall {
+    pub callee: Box<dyn QalamCallable>,
+    pub arguments: Vec<Option<Literal>>,
+    pub paren: Token,
+}
+
+/// An error, or a `wapis`, `exit` or tail call unwinding the program
+/// The details are boxed so the `Result`s returned all through the interpreter stay small
+#[derive(Debug)]
+pub struct RuntimeError(Box<RuntimeErrorDetails>);
+
+#[derive(Debug)]
+pub struct RuntimeErrorDetails {
     pub message: String,
     pub token: Token,
     pub return_value: Option<Literal>,
+    pub limit: Option<LimitKind>,
+    /// Status the program asked to exit with by calling `exit`
+    pub exit_code: Option<i32>,
+    pub trace: Vec<CallFrame>,
+    pub tail_call: Option<Box<TailCall>>,
+    /// The argument that was wrong, for errors about the arguments of natives
+    pub argument: Option<ArgumentError>,
 }
 
 impl std::fmt::Display for RuntimeError {
@@ -103,20 +257,102 @@ impl std::fmt::Display for RuntimeError {
 
 impl std::error::Error for RuntimeError {}
 
+impl std::ops::Deref for RuntimeError {
+    type Target = RuntimeErrorDetails;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for RuntimeError {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 impl RuntimeError {
     pub fn init(token: &Token, message: String) -> Self {
-        return Self {
+        return Self(Box::new(RuntimeErrorDetails {
             token: Token::copy(token),
             message,
             return_value: None,
-        };
+            limit: None,
+            exit_code: None,
+            trace: Vec::new(),
+            tail_call: None,
+            argument: None,
+        }));
+    }
+
+    /// Error for an argument of a native with the wrong type or value
+    pub fn init_argument(token: &Token, argument: ArgumentError) -> Self {
+        let mut error = Self::init(token, argument.message());
+        error.argument = Some(argument);
+        return error;
+    }
+
+    pub fn init_tail_call(
+        callee: Box<dyn QalamCallable>,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Self {
+        return Self(Box::new(RuntimeErrorDetails {
+            token: Token::copy(paren),
+            message: String::from("tail call"),
+            return_value: None,
+            limit: None,
+            exit_code: None,
+            trace: Vec::new(),
+            tail_call: Some(Box::new(TailCall {
+                callee,
+                arguments,
+                paren: Token::copy(paren),
+            })),
+            argument: None,
+        }));
+    }
+
+    /// Error for when an execution limit is exceeded
+    /// Hosts can check `limit` to tell these apart from errors in the program
+    pub fn init_limit(token: &Token, kind: LimitKind, message: String) -> Self {
+        return Self(Box::new(RuntimeErrorDetails {
+            token: Token::copy(token),
+            message,
+            return_value: None,
+            limit: Some(kind),
+            exit_code: None,
+            trace: Vec::new(),
+            tail_call: None,
+            argument: None,
+        }));
+    }
+
+    /// Error that unwinds the program when it calls `exit`
+    /// Hosts check `exit_code` to end with that status instead of reporting an error
+    pub fn init_exit(token: &Token, code: i32) -> Self {
+        return Self(Box::new(RuntimeErrorDetails {
+            token: Token::copy(token),
+            message: format!("Program exited with status {}.", code),
+            return_value: None,
+            limit: None,
+            exit_code: Some(code),
+            trace: Vec::new(),
+            tail_call: None,
+            argument: None,
+        }));
     }
 
     pub fn init_return(value: Option<Literal>) -> Self {
-        return Self {
+        return Self(Box::new(RuntimeErrorDetails {
             token: Token::dummy(),
             message: String::from("dummy"),
             return_value: value,
-        };
+            limit: None,
+            exit_code: None,
+            trace: Vec::new(),
+            tail_call: None,
+            argument: None,
+        }));
     }
 }
@@ -0,0 +1,326 @@
+use crate::error::{ErrorReporter, ReportedError};
+use crate::parser::Parser;
+use crate::scanner::Scanner;
+use crate::token::{Token, TokenType};
+
+const INDENT: &str = "  ";
+
+/// Formats qalam source code into the canonical style
+///
+/// ### Arguments
+/// `source` - the source code to format
+/// ### Returns
+/// `Result<String, ReportedError>` - the formatted source or the first syntax error
+pub fn format_source(source: &str) -> Result<String, ReportedError> {
+    let source = source.to_string();
+
+    // The source is parsed first so that only valid programs are formatted
+    let mut reporter = ErrorReporter::init_quiet();
+    let mut scanner = Scanner::init(&source, &mut reporter);
+    let tokens = scanner.scan_tokens().clone();
+    if let Err(e) = Parser::init(&tokens).parse() {
+        reporter.error_token(&e.token, &e.message, crate::error::ErrorType::Syntax);
+    }
+    if let Some(error) = reporter.errors.first() {
+        return Err(error.clone());
+    }
+
+    let mut reporter = ErrorReporter::init_quiet();
+    let mut scanner = Scanner::init_with_comments(&source, &mut reporter);
+    let tokens = scanner.scan_tokens().clone();
+    return Ok(Formatter::init(tokens).format());
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum BraceKind {
+    Block,
+    Class,
+}
+
+pub struct Formatter {
+    tokens: Vec<Token>,
+    out: String,
+    indent: usize,
+    /// Open parentheses and square brackets, `true` for array literals with each element on its own line
+    brackets: Vec<bool>,
+    braces: Vec<BraceKind>,
+    in_class_header: bool,
+    pending_newline: bool,
+    force_blank_line: bool,
+    /// Source line the previous token (or comment) ended on
+    last_line: i64,
+    previous: Option<TokenType>,
+    previous_unary: bool,
+}
+
+impl Formatter {
+    pub fn init(tokens: Vec<Token>) -> Self {
+        return Self {
+            tokens: tokens
+                .into_iter()
+                .filter(|token| token.token_type != TokenType::Eof)
+                .collect(),
+            out: String::new(),
+            indent: 0,
+            brackets: Vec::new(),
+            braces: Vec::new(),
+            in_class_header: false,
+            pending_newline: false,
+            force_blank_line: false,
+            last_line: 0,
+            previous: None,
+            previous_unary: false,
+        };
+    }
+
+    pub fn format(mut self) -> String {
+        let mut i = 0;
+        while i < self.tokens.len() {
+            let token = self.tokens[i].clone();
+            if token.token_type == TokenType::Comment {
+                self.comment(&token);
+                i += 1;
+                continue;
+            }
+
+            // Empty blocks are written as `{}`
+            let next = self.next_significant(i + 1);
+            if token.token_type == TokenType::LeftBrace
+                && next.map(|(_, t)| t.token_type) == Some(TokenType::RightBrace)
+                && next.map(|(j, _)| j) == Some(i + 1)
+            {
+                self.write_token(&token, "{}");
+                self.in_class_header = false;
+                self.last_line = self.tokens[i + 1].line;
+                self.previous = Some(TokenType::RightBrace);
+                self.after_right_brace(i + 1);
+                i += 2;
+                continue;
+            }
+
+            let closes_array = token.token_type == TokenType::RightSquare
+                && self.brackets.last() == Some(&true);
+            if token.token_type == TokenType::RightBrace || closes_array {
+                self.indent = self.indent.saturating_sub(1);
+                self.pending_newline = true;
+            }
+            let opens_array = token.token_type == TokenType::LeftSquare
+                && !self.previous.map(Self::ends_operand).unwrap_or(false);
+            self.write_token(&token, token.lexeme.as_str());
+            self.last_line = token.line;
+            self.previous_unary = self.is_unary(&token);
+            self.previous = Some(token.token_type);
+
+            match token.token_type {
+                TokenType::LeftParen => self.brackets.push(false),
+                TokenType::LeftSquare => {
+                    let one_per_line = opens_array && self.breaks_array(i);
+                    if one_per_line {
+                        self.indent += 1;
+                        self.pending_newline = true;
+                    }
+                    self.brackets.push(one_per_line);
+                }
+                TokenType::RightParen | TokenType::RightSquare => {
+                    self.brackets.pop();
+                }
+                TokenType::Comma if self.brackets.last() == Some(&true) => {
+                    self.pending_newline = true
+                }
+                TokenType::Semicolon if self.brackets.is_empty() => self.pending_newline = true,
+                TokenType::Class => self.in_class_header = true,
+                TokenType::LeftBrace => {
+                    let kind = match self.in_class_header {
+                        true => BraceKind::Class,
+                        false => BraceKind::Block,
+                    };
+                    self.in_class_header = false;
+                    self.braces.push(kind);
+                    self.indent += 1;
+                    self.pending_newline = true;
+                }
+                TokenType::RightBrace => {
+                    self.braces.pop();
+                    self.after_right_brace(i);
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+        return self.out;
+    }
+
+    /// Decides what follows a closing brace: `warna` stays on the same line and
+    /// methods of a jamat are separated by a blank line
+    fn after_right_brace(&mut self, index: usize) {
+        let next = self.next_significant(index + 1).map(|(_, t)| t.token_type);
+        if next == Some(TokenType::Else) {
+            self.pending_newline = false;
+            return;
+        }
+        self.pending_newline = true;
+        if self.braces.last() == Some(&BraceKind::Class) && next != Some(TokenType::RightBrace) {
+            self.force_blank_line = true;
+        }
+    }
+
+    /// Checks if the array literal opened at `index` has a comment or a line break in it,
+    /// in which case each element goes on its own line
+    fn breaks_array(&self, index: usize) -> bool {
+        let open = &self.tokens[index];
+        let mut depth = 0;
+        for token in self.tokens.iter().skip(index) {
+            match token.token_type {
+                TokenType::LeftSquare => depth += 1,
+                TokenType::RightSquare => depth -= 1,
+                _ => {}
+            }
+            if token.token_type == TokenType::Comment || token.line != open.line {
+                return true;
+            }
+            if depth == 0 {
+                return false;
+            }
+        }
+        return false;
+    }
+
+    fn next_significant(&self, from: usize) -> Option<(usize, &Token)> {
+        return self
+            .tokens
+            .iter()
+            .enumerate()
+            .skip(from)
+            .find(|(_, t)| t.token_type != TokenType::Comment);
+    }
+
+    fn comment(&mut self, token: &Token) {
+        let text = token.lexeme.trim_end();
+        if self.previous.is_some() && token.line == self.last_line && !self.at_line_start() {
+            // Trailing comments stay on the line they were written on
+            self.out.push(' ');
+            self.out.push_str(text);
+        } else {
+            self.start_line(token.line, false);
+            self.out.push_str(text);
+        }
+        self.pending_newline = true;
+        self.last_line = token.line;
+    }
+
+    fn write_token(&mut self, token: &Token, text: &str) {
+        let start_line = token.line - token.lexeme.matches('\n').count() as i64;
+        if self.pending_newline || self.at_line_start() {
+            let closing = matches!(token.token_type, TokenType::RightBrace | TokenType::RightSquare);
+            self.start_line(start_line, closing);
+        } else if self.needs_space(token) {
+            self.out.push(' ');
+        }
+        self.out.push_str(text);
+    }
+
+    /// Starts a new line, keeping at most one blank line from the source
+    fn start_line(&mut self, start_line: i64, closing: bool) {
+        if !self.at_line_start() {
+            self.out.push('\n');
+        }
+        let blank = self.force_blank_line || start_line > self.last_line + 1;
+        if blank
+            && !self.out.is_empty()
+            && !closing
+            && self.previous != Some(TokenType::LeftBrace)
+            && !self.out.ends_with("\n\n")
+        {
+            self.out.push('\n');
+        }
+        self.force_blank_line = false;
+        self.pending_newline = false;
+
+        // Lines inside parentheses are indented once more, elements of an array that has one
+        // on each line are indented by the array
+        let continuation = match self.brackets.last() {
+            Some(false) => 1,
+            _ => 0,
+        };
+        self.out.push_str(&INDENT.repeat(self.indent + continuation));
+    }
+
+    fn at_line_start(&self) -> bool {
+        return self.out.is_empty() || self.out.ends_with('\n');
+    }
+
+    /// Checks if a token ends an operand, which decides whether the next `-` is unary
+    fn ends_operand(token_type: TokenType) -> bool {
+        return matches!(
+            token_type,
+            TokenType::Identifier
+                | TokenType::Number
+                | TokenType::String
+                | TokenType::True
+                | TokenType::False
+                | TokenType::Nil
+                | TokenType::This
+                | TokenType::RightParen
+                | TokenType::RightSquare
+                | TokenType::Increment
+                | TokenType::Decrement
+        );
+    }
+
+    fn is_unary(&self, token: &Token) -> bool {
+        let operand_before = self.previous.map(Self::ends_operand).unwrap_or(false);
+        return match token.token_type {
+            TokenType::Minus => !operand_before,
+            TokenType::Bang => token.lexeme == "!",
+            _ => false,
+        };
+    }
+
+    fn needs_space(&self, token: &Token) -> bool {
+        let previous = match self.previous {
+            Some(previous) => previous,
+            None => return false,
+        };
+
+        match token.token_type {
+            TokenType::RightParen
+            | TokenType::RightSquare
+            | TokenType::Comma
+            | TokenType::Semicolon => return false,
+            // A rest parameter after a comma, e.g. `(a, ...rest)`
+            TokenType::Dot if !matches!(previous, TokenType::Comma) => return false,
+            TokenType::Increment | TokenType::Decrement if Self::ends_operand(previous) => {
+                return false
+            }
+            _ => {}
+        }
+
+        if self.previous_unary
+            || matches!(
+                previous,
+                TokenType::LeftParen | TokenType::LeftSquare | TokenType::Dot
+            )
+        {
+            return false;
+        }
+
+        return match token.token_type {
+            TokenType::LeftParen => !matches!(
+                previous,
+                TokenType::Identifier
+                    | TokenType::RightParen
+                    | TokenType::RightSquare
+                    | TokenType::If
+                    | TokenType::For
+                    | TokenType::While
+                    | TokenType::Print
+            ),
+            TokenType::LeftSquare => !Self::ends_operand(previous),
+            _ => true,
+        };
+    }
+}
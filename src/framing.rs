@@ -0,0 +1,49 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::io::{BufRead, Write};
+
+/// Reads the body of a message framed by a `Content-Length` header, the base protocol
+/// shared by the Language Server Protocol and the Debug Adapter Protocol
+///
+/// ### Returns
+/// `Result<Option<Vec<u8>>>` - the body, or `None` once the input is closed
+pub fn read_frame<R: BufRead + ?Sized>(input: &mut R) -> Result<Option<Vec<u8>>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            length = Some(value.trim().parse::<usize>()?);
+        }
+    }
+
+    let length = length.context("Message is missing a Content-Length header")?;
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    return Ok(Some(body));
+}
+
+/// Reads a JSON message framed by a `Content-Length` header
+///
+/// ### Returns
+/// `Result<Option<Value>>` - the message, or `None` once the input is closed
+pub fn read_framed_message<R: BufRead + ?Sized>(input: &mut R) -> Result<Option<Value>> {
+    return match read_frame(input)? {
+        Some(body) => Ok(Some(serde_json::from_slice(&body)?)),
+        None => Ok(None),
+    };
+}
+
+/// Writes a JSON message with the `Content-Length` header `read_framed_message` expects
+pub fn write_framed_message<W: Write + ?Sized>(output: &mut W, message: &Value) -> Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()?;
+    return Ok(());
+}
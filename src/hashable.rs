@@ -42,6 +42,11 @@ where
         self.map.remove(key)
     }
 
+    // Iterate over the key-value pairs
+    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, K, V> {
+        self.map.iter()
+    }
+
     // Get the number of elements in the map
     pub fn len(&self) -> usize {
         self.map.len()
@@ -107,11 +112,10 @@ impl<T> Clone for HashableRcRefCell<T> {
     }
 }
 
+// Shared values are equal only if they are the same reference
 impl<T: PartialEq> PartialEq for HashableRcRefCell<T> {
     fn eq(&self, other: &Self) -> bool {
-        let self_borrowed = self.0.borrow();
-        let other_borrowed = other.0.borrow();
-        *self_borrowed == *other_borrowed
+        Rc::ptr_eq(&self.0, &other.0)
     }
 }
 
@@ -6,41 +6,117 @@ use crate::callable::class::QalamClass;
 use crate::callable::function::QalamFunction;
 use crate::callable::instance::QalamInstance;
 use crate::callable::QalamCallable;
+use crate::debugger::Debugger;
 use crate::environment::Environment;
-use crate::error::RuntimeError;
+use crate::error::{CallFrame, RuntimeError, TailCall};
 use crate::hashable::{HashableMap, HashableRcRefCell};
+use crate::limits::{ExecutionLimits, LimitKind};
+use crate::methods::{
+    MethodType, Methods, ARRAY_METHODS, NUMBER_MATH_METHODS, NUMBER_METHODS, STRING_METHODS,
+};
+use crate::permissions::Permissions;
 use crate::literal::{Literal, QalamArray};
+use crate::native::array::{ConcatFn, InsertFn, RangeFn, RemoveAtFn, SortFn, SumFn};
 use crate::native::array_constructor::ArrayConstructorFn;
+use crate::native::barabar::BarabarFn;
 use crate::native::ceil::CeilFn;
 use crate::native::clock::ClockFn;
 use crate::native::code::CodeFn;
 use crate::native::floor::FloorFn;
 use crate::native::indexof::IndexOfFn;
 use crate::native::is_usize;
+use crate::native::fs::{ListDirFn, PathExistsFn, ReadFileFn, ReadLinesFn, WriteFileFn};
+use crate::native::json::{JsonParseFn, JsonStringifyFn};
 use crate::native::len::LenFn;
+use crate::native::math::{math_namespace, MATH_GLOBAL};
 use crate::native::max::MaxFn;
 use crate::native::min::MinFn;
 use crate::native::num::NumFn;
 use crate::native::pop::PopFn;
 use crate::native::pow::PowFn;
+use crate::native::process::{ExitFn, PuchoFn};
 use crate::native::push::PushFn;
 use crate::native::random::RandomFn;
 use crate::native::random_int::RandomIntFn;
 use crate::native::replace::ReplaceFn;
+use crate::native::seed::seed_fn;
 use crate::native::round::RoundFn;
 use crate::native::slice::SliceFn;
 use crate::native::str::StrFn;
+use crate::native::string::{
+    CharsFn, ContainsFn, FormatFn, JoinFn, PadFn, RepeatFn, ReverseFn, SplitFn, StringMapFn, StringTestFn,
+};
 use crate::native::substr::SubstrFn;
 use crate::native::typeof_func::TypeofFn;
 use crate::token::{Token, TokenType};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::io::{BufRead, Write};
+use std::time::Instant;
+
+/// Default for the maximum number of nested calls before a stack overflow is reported
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 1000;
+
+/// Largest maximum call depth that can be asked for, so deep recursion is reported before it
+/// overflows the interpreter thread's 256 MB stack. Calls with deeply nested expressions take
+/// more of the stack, and debug builds take several times more, so this leaves room for both
+pub const MAX_CALL_DEPTH: usize = 4000;
+
+/// Stack size the interpreter assumes its thread has unless told otherwise, that of the main
+/// thread on most platforms. Threads spawned with `std::thread::spawn` get 2 MB
+pub const DEFAULT_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Global array holding the command-line arguments after the file path
+pub const ARGS_GLOBAL: &str = "args";
+
+/// Seed of the random number generator in deterministic mode when no seed is given
+pub const DEFAULT_SEED: u64 = 0;
+
+/// Number of steps between checks of the clock when a timeout is set
+const DEADLINE_CHECK_INTERVAL: u64 = 128;
 
 pub struct Interpreter {
     pub globals: Rc<RefCell<Environment>>,
     pub environment: Rc<RefCell<Environment>>,
     pub locals: HashMap<Expr, usize>,
+    pub frames: Vec<CallFrame>,
+    /// Environment each frame in `frames` was called from, which its caller returns to
+    caller_environments: Vec<Rc<RefCell<Environment>>>,
+    pub max_call_depth: usize,
+    /// Size of the stack of the thread the interpreter runs on. Calls are stopped with a stack
+    /// overflow error once they use three quarters of it, whatever `max_call_depth` is
+    pub stack_size: usize,
+    /// Address on the stack where the outermost call started
+    stack_base: usize,
+    pub limits: ExecutionLimits,
+    /// Methods of strings, arrays and numbers, natives can add to these
+    pub methods: Methods,
+    /// Paths the file system natives may access
+    pub permissions: Permissions,
+    /// Random number generator used by all the random natives
+    pub rng: StdRng,
+    /// Time in milliseconds returned by `clock` in deterministic mode, `None` for the real time
+    /// Counted in whole milliseconds so that adding up ticks doesn't drift
+    pub virtual_clock: Option<u64>,
+    steps: u64,
+    allocated: usize,
+    deadline: Option<Instant>,
+    current_line: i64,
+    /// Where `bolo` writes to
+    pub output: Box<dyn Write>,
+    /// Where `pucho` reads from, stdin if not set
+    pub input: Option<Box<dyn BufRead>>,
+    pub debugger: Option<Debugger>,
+    /// Looks up variables missing from `locals` through the environment chain instead
+    /// of the globals, for expressions that weren't resolved (e.g. typed in a debugger)
+    dynamic_scope: bool,
+    /// Prints the value of top level expression statements, for the REPL
+    pub echo: bool,
+    /// Value of the last expression statement
+    last_value: Option<Literal>,
 }
 
 impl Interpreter {
@@ -59,6 +135,7 @@ impl Interpreter {
         Self::add_global(globals.clone(), "replace", ReplaceFn::init());
         Self::add_global(globals.clone(), "random", RandomFn::init());
         Self::add_global(globals.clone(), "random_int", RandomIntFn::init());
+        Self::add_global(globals.clone(), "seed", seed_fn());
         Self::add_global(globals.clone(), "push", PushFn::init());
         Self::add_global(globals.clone(), "pop", PopFn::init());
         Self::add_global(globals.clone(), "Array", ArrayConstructorFn::init());
@@ -67,10 +144,59 @@ impl Interpreter {
         Self::add_global(globals.clone(), "ceil", CeilFn::init());
         Self::add_global(globals.clone(), "round", RoundFn::init());
         Self::add_global(globals.clone(), "slice", SliceFn::init());
+        Self::add_global(globals.clone(), "barabar", BarabarFn::init());
+        Self::add_global(globals.clone(), "json_parse", JsonParseFn::init());
+        Self::add_global(globals.clone(), "json_stringify", JsonStringifyFn::init(false));
+        Self::add_global(globals.clone(), "json_pretty", JsonStringifyFn::init(true));
+        Self::add_global(globals.clone(), "read_file", ReadFileFn::init());
+        Self::add_global(globals.clone(), "read_lines", ReadLinesFn::init());
+        Self::add_global(globals.clone(), "write_file", WriteFileFn::init());
+        Self::add_global(globals.clone(), "list_dir", ListDirFn::init());
+        Self::add_global(globals.clone(), "path_exists", PathExistsFn::init());
+        Self::add_global(globals.clone(), "pucho", PuchoFn::init());
+        Self::add_global(globals.clone(), "exit", ExitFn::init());
+        Self::add_global(globals.clone(), "format", FormatFn::init());
+        Self::add_global(globals.clone(), "range", RangeFn::init());
+        globals
+            .borrow_mut()
+            .define(String::from(ARGS_GLOBAL), Self::args_array(&[]));
+        let math = math_namespace();
+        globals
+            .borrow_mut()
+            .define(String::from(MATH_GLOBAL), math.clone());
+
+        let mut methods = Methods::init();
+        methods.add_from(MethodType::String, &STRING_METHODS, globals.borrow().iter());
+        methods.add_from(MethodType::Array, &ARRAY_METHODS, globals.borrow().iter());
+        methods.add_from(MethodType::Number, &NUMBER_METHODS, globals.borrow().iter());
+        if let Some(Literal::Instance(math)) = math {
+            methods.add_from(MethodType::Number, &NUMBER_MATH_METHODS, math.0.borrow().fields.iter());
+        }
+        Self::add_methods(&mut methods);
         return Self {
             globals: globals.clone(),
+            methods,
             environment: globals.clone(),
             locals: HashMap::new(),
+            frames: Vec::new(),
+            caller_environments: Vec::new(),
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            stack_size: DEFAULT_STACK_SIZE,
+            stack_base: 0,
+            limits: ExecutionLimits::init(),
+            permissions: Permissions::init(),
+            rng: StdRng::from_entropy(),
+            virtual_clock: None,
+            steps: 0,
+            allocated: 0,
+            deadline: None,
+            current_line: 1,
+            output: Box::new(std::io::stdout()),
+            input: None,
+            debugger: None,
+            dynamic_scope: false,
+            echo: false,
+            last_value: None,
         };
     }
 
@@ -79,7 +205,90 @@ impl Interpreter {
         return Ok(());
     }
 
-    fn add_global<F>(globals: Rc<RefCell<Environment>>, name: &str, func: F)
+    fn args_array(args: &[String]) -> Option<Literal> {
+        let args = args
+            .iter()
+            .map(|arg| Some(Literal::String(arg.to_string())))
+            .collect();
+        return Some(Literal::Array(HashableRcRefCell::init(QalamArray::from_vec(
+            args,
+        ))));
+    }
+
+    /// Restarts the random number generator from a seed, so the same numbers are generated each run
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Makes runs reproducible: random numbers are generated from `seed` and `clock`
+    /// returns a virtual time starting at 0 that advances by the same amount on each call
+    pub fn set_deterministic(&mut self, seed: u64) {
+        self.seed(seed);
+        self.virtual_clock = Some(0);
+    }
+
+    /// Adds the natives that only work on strings or arrays, which are methods rather than globals
+    fn add_methods(methods: &mut Methods) {
+        methods.add(MethodType::String, "split", SplitFn::init());
+        methods.add(
+            MethodType::String,
+            "trim",
+            StringMapFn::init("trim", |s| s.trim().to_string()),
+        );
+        methods.add(
+            MethodType::String,
+            "trim_start",
+            StringMapFn::init("trim_start", |s| s.trim_start().to_string()),
+        );
+        methods.add(
+            MethodType::String,
+            "trim_end",
+            StringMapFn::init("trim_end", |s| s.trim_end().to_string()),
+        );
+        methods.add(
+            MethodType::String,
+            "upper",
+            StringMapFn::init("upper", str::to_uppercase),
+        );
+        methods.add(
+            MethodType::String,
+            "lower",
+            StringMapFn::init("lower", str::to_lowercase),
+        );
+        methods.add(
+            MethodType::String,
+            "starts_with",
+            StringTestFn::init("starts_with", "prefix", |s, prefix| s.starts_with(prefix)),
+        );
+        methods.add(
+            MethodType::String,
+            "ends_with",
+            StringTestFn::init("ends_with", "suffix", |s, suffix| s.ends_with(suffix)),
+        );
+        methods.add(MethodType::String, "repeat", RepeatFn::init());
+        methods.add(MethodType::String, "pad_start", PadFn::init(false));
+        methods.add(MethodType::String, "pad_end", PadFn::init(true));
+        methods.add(MethodType::String, "chars", CharsFn::init());
+        methods.add(MethodType::Array, "join", JoinFn::init());
+        methods.add(MethodType::Array, "insert", InsertFn::init());
+        methods.add(MethodType::Array, "remove_at", RemoveAtFn::init());
+        methods.add(MethodType::Array, "concat", ConcatFn::init());
+        methods.add(MethodType::Array, "sum", SumFn::init());
+        methods.add(MethodType::Array, "sort", SortFn::init());
+        for method_type in [MethodType::String, MethodType::Array] {
+            methods.add(method_type, "contains", ContainsFn::init());
+            methods.add(method_type, "reverse", ReverseFn::init());
+        }
+    }
+
+    /// Sets the `args` global to the command-line arguments passed to the program
+    pub fn set_args(&mut self, args: &[String]) {
+        self.globals
+            .borrow_mut()
+            .define(String::from(ARGS_GLOBAL), Self::args_array(args));
+    }
+
+    pub fn add_global<F>(globals: Rc<RefCell<Environment>>, name: &str, func: F)
     where
         F: QalamCallable + 'static,
     {
@@ -88,10 +297,210 @@ impl Interpreter {
             .define(name.to_string(), Some(Literal::Callable(Box::new(func))))
     }
 
+    /// Calls a callable with already evaluated arguments
+    /// Checks the arity and keeps track of the call stack
+    /// Calls in tail position replace the frame of the caller instead of nesting
+    pub fn call(
+        &mut self,
+        mut callee: Box<dyn QalamCallable>,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        Self::check_arity(callee.as_ref(), &arguments, paren)?;
+        if self.frames.len() >= self.max_call_depth {
+            let message = format!(
+                "Stack overflow: maximum call depth of {} exceeded calling '{}'.",
+                self.max_call_depth,
+                callee.name()
+            );
+            return Err(self.stack_overflow(message, paren));
+        }
+        // Each call nests many Rust calls, so the Rust stack can run out before the maximum
+        // depth, e.g. on a small thread or in a debug build. The rest of it is left for the
+        // host's frames below the outermost call and the expressions of the innermost one
+        let marker = 0u8;
+        let address = std::ptr::addr_of!(marker) as usize;
+        if self.frames.is_empty() {
+            self.stack_base = address;
+        } else if address.abs_diff(self.stack_base) > self.stack_size / 4 * 3 {
+            let message = format!(
+                "Stack overflow: out of stack space at a call depth of {} calling '{}'.",
+                self.frames.len(),
+                callee.name()
+            );
+            return Err(self.stack_overflow(message, paren));
+        }
+
+        self.caller_environments.push(self.environment.clone());
+        self.frames.push(CallFrame {
+            name: callee.name(),
+            call_site: paren.clone(),
+            tail_calls: 0,
+        });
+        let mut result = callee.call(self, arguments, paren);
+        while let Err(e) = &mut result {
+            let tail_call = match e.tail_call.take() {
+                Some(tail_call) => *tail_call,
+                None => break,
+            };
+            let TailCall {
+                callee: mut tail_callee,
+                arguments,
+                paren,
+            } = tail_call;
+            if let Err(mut e) = Self::check_arity(tail_callee.as_ref(), &arguments, &paren) {
+                e.trace = self.frames.clone();
+                result = Err(e);
+                break;
+            }
+            // The call site stays the caller's, which is where the frame is returning to
+            let frame = self.frames.last_mut().unwrap();
+            frame.name = tail_callee.name();
+            frame.tail_calls += 1;
+            result = tail_callee.call(self, arguments, &paren);
+        }
+        if let Err(e) = &mut result {
+            // The innermost frame records the call stack as it was when the error happened
+            if e.trace.is_empty() {
+                e.trace = self.frames.clone();
+            }
+        }
+        self.frames.pop();
+        self.caller_environments.pop();
+        return result;
+    }
+
+    fn check_arity(
+        callee: &dyn QalamCallable,
+        arguments: &[Option<Literal>],
+        paren: &Token,
+    ) -> Result<(), RuntimeError> {
+        let (min, max) = (callee.arity(), callee.max_arity());
+        if arguments.len() >= min && max.map(|max| arguments.len() <= max).unwrap_or(true) {
+            return Ok(());
+        }
+        let expected = match max {
+            Some(max) if max == min => format!("{}", min),
+            Some(max) => format!("{} to {}", min, max),
+            None => format!("at least {}", min),
+        };
+        return Err(RuntimeError::init(
+            paren,
+            format!("Expected {} arguments but got {}.", expected, arguments.len()),
+        ));
+    }
+
+    fn stack_overflow(&self, message: String, paren: &Token) -> RuntimeError {
+        let mut error = RuntimeError::init(paren, message);
+        error.trace = self.frames.clone();
+        return error;
+    }
+
     fn evaluate(&mut self, expr: &Expr) -> Result<Option<Literal>, RuntimeError> {
+        if let Some(kind) = self.tick() {
+            return Err(self.limit_error(kind, expr.token()));
+        }
         expr.accept(self)
     }
 
+    /// Counts a step of execution and checks the step and time limits
+    fn tick(&mut self) -> Option<LimitKind> {
+        self.steps += 1;
+        if let Some(max_steps) = self.limits.max_steps {
+            if self.steps > max_steps {
+                return Some(LimitKind::Steps);
+            }
+        }
+
+        if let Some(deadline) = self.deadline {
+            if self.steps.is_multiple_of(DEADLINE_CHECK_INTERVAL) && Instant::now() >= deadline {
+                return Some(LimitKind::Timeout);
+            }
+        }
+        return None;
+    }
+
+    /// Checks the timeout from inside a native, whose loops aren't counted as steps
+    ///
+    /// ### Returns
+    /// `Result<(), RuntimeError>` - a `LimitError` at `token` if the program has run out of time
+    pub fn check_deadline(&self, token: &Token) -> Result<(), RuntimeError> {
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err(self.limit_error(LimitKind::Timeout, Some(token)));
+        }
+        return Ok(());
+    }
+
+    fn limit_error(&self, kind: LimitKind, token: Option<&Token>) -> RuntimeError {
+        let token = match token {
+            Some(token) => token.clone(),
+            None => self.line_token(),
+        };
+        return RuntimeError::init_limit(&token, kind, kind.message(&self.limits));
+    }
+
+    /// Placeholder token at the line of the statement being executed
+    fn line_token(&self) -> Token {
+        return Token::init(TokenType::Eof, &String::new(), None, self.current_line, 0, 0);
+    }
+
+    /// Records memory allocated for an array or string and checks the memory limit
+    pub fn allocate(&mut self, bytes: usize, token: &Token) -> Result<(), RuntimeError> {
+        self.allocated = self.allocated.saturating_add(bytes);
+        if let Some(max_memory) = self.limits.max_memory {
+            if self.allocated > max_memory {
+                return Err(self.limit_error(LimitKind::Memory, Some(token)));
+            }
+        }
+        return Ok(());
+    }
+
+    /// Creates an empty string with room for `bytes` bytes, accounted in the memory limit
+    ///
+    /// ### Returns
+    /// `Result<String, RuntimeError>` - the string, or an error instead of aborting if the
+    /// memory can't be allocated
+    pub fn string_with_capacity(&mut self, bytes: usize, token: &Token) -> Result<String, RuntimeError> {
+        self.allocate(bytes, token)?;
+        let mut string = String::new();
+        if string.try_reserve_exact(bytes).is_err() {
+            return Err(RuntimeError::init(
+                token,
+                format!("Cannot allocate a string of {} bytes!", bytes),
+            ));
+        }
+        return Ok(string);
+    }
+
+    /// Records memory allocated for `count` array elements
+    pub fn allocate_elements(&mut self, count: usize, token: &Token) -> Result<(), RuntimeError> {
+        return self.allocate(
+            count.saturating_mul(std::mem::size_of::<Option<Literal>>()),
+            token,
+        );
+    }
+
+    /// Creates an empty array of elements with room for `count` elements, accounted in the memory limit
+    ///
+    /// ### Returns
+    /// `Result<Vec<Option<Literal>>, RuntimeError>` - the elements, or an error instead of aborting
+    /// if the memory can't be allocated
+    pub fn elements_with_capacity(
+        &mut self,
+        count: usize,
+        token: &Token,
+    ) -> Result<Vec<Option<Literal>>, RuntimeError> {
+        self.allocate_elements(count, token)?;
+        let mut elements = Vec::new();
+        if elements.try_reserve_exact(count).is_err() {
+            return Err(RuntimeError::init(
+                token,
+                format!("Cannot allocate an array of {} elements!", count),
+            ));
+        }
+        return Ok(elements);
+    }
+
     fn flip_bool(value: bool, flip: bool) -> bool {
         if flip {
             return !value;
@@ -113,34 +522,9 @@ impl Interpreter {
     }
 
     fn is_equal(&mut self, a: Option<Literal>, b: Option<Literal>, flip: bool) -> Option<Literal> {
-        match a {
-            Some(a_val) => match b {
-                Some(b_val) => {
-                    if let (Literal::Number(a_val), Literal::Number(b_val)) =
-                        (a_val.clone(), b_val.clone())
-                    {
-                        return Some(Literal::Bool(Self::flip_bool(a_val == b_val, flip)));
-                    }
-
-                    if let (Literal::Bool(a_val), Literal::Bool(b_val)) =
-                        (a_val.clone(), b_val.clone())
-                    {
-                        return Some(Literal::Bool(Self::flip_bool(a_val == b_val, flip)));
-                    }
-
-                    if let (Literal::String(a_val), Literal::String(b_val)) = (a_val, b_val) {
-                        return Some(Literal::Bool(Self::flip_bool(a_val == b_val, flip)));
-                    }
-
-                    return Some(Literal::Bool(Self::flip_bool(false, flip)));
-                }
-                None => return Some(Literal::Bool(Self::flip_bool(false, flip))),
-            },
-            None => match b {
-                Some(_) => return Some(Literal::Bool(Self::flip_bool(false, flip))),
-                None => return Some(Literal::Bool(Self::flip_bool(true, flip))),
-            },
-        }
+        // Numbers, strings and bools compare by value
+        // Arrays, instances and callables compare by reference
+        return Some(Literal::Bool(Self::flip_bool(a == b, flip)));
     }
 
     pub fn execute_block(
@@ -166,16 +550,85 @@ impl Interpreter {
     }
 
     pub fn interpret(&mut self, mut statements: Vec<Stmt>) -> Result<(), RuntimeError> {
+        self.steps = 0;
+        self.allocated = 0;
+        self.deadline = self.limits.timeout.map(|timeout| Instant::now() + timeout);
+        self.current_line = 1;
         for stmt in statements.iter_mut() {
             self.execute(stmt)?;
+            if !self.echo || !matches!(stmt, Stmt::Expression { .. }) {
+                continue;
+            }
+            // khali isn't echoed, so calls made for their side effects stay quiet
+            if let Some(value) = self.last_value.take() {
+                let _ = writeln!(self.output, "{}", value.to_qalam_string());
+            }
         }
         Ok(())
     }
 
     fn execute(&mut self, stmt: &mut Stmt) -> Result<(), RuntimeError> {
+        if let Some(token) = stmt.token() {
+            self.current_line = token.line;
+        }
+        // Blocks only group statements, so the debugger pauses at the statements inside
+        if self.debugger.is_some() && !matches!(stmt, Stmt::Block { .. }) {
+            let mut debugger = self.debugger.take().unwrap();
+            debugger.before_statement(self, stmt.token());
+            self.debugger = Some(debugger);
+        }
+        if let Some(kind) = self.tick() {
+            return Err(self.limit_error(kind, stmt.token()));
+        }
         stmt.accept(self)
     }
 
+    /// Line of the statement being executed
+    pub fn current_line(&self) -> i64 {
+        return self.current_line;
+    }
+
+    /// Lists the calls being executed with the line each one is at, innermost first
+    pub fn call_stack(&self) -> Vec<(String, i64)> {
+        let mut stack = vec![(String::from("<script>"), self.current_line)];
+        for frame in self.frames.iter() {
+            // The call site of a frame is the line its caller is at
+            stack.last_mut().unwrap().1 = frame.call_site.line;
+            stack.push((frame.name.to_string(), self.current_line));
+        }
+        stack.reverse();
+        return stack;
+    }
+
+    /// Environments of the frames of `call_stack`, innermost first
+    pub fn frame_environments(&self) -> Vec<Rc<RefCell<Environment>>> {
+        let mut environments = vec![self.environment.clone()];
+        environments.extend(self.caller_environments.iter().rev().cloned());
+        return environments;
+    }
+
+    /// Evaluates an expression with `environment` as the current environment, which is
+    /// restored afterwards, e.g. a parameter's default value in the scope of the call
+    pub fn evaluate_in(
+        &mut self,
+        expr: &Expr,
+        environment: Rc<RefCell<Environment>>,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let previous = std::mem::replace(&mut self.environment, environment);
+        let result = self.evaluate(expr);
+        self.environment = previous;
+        return result;
+    }
+
+    /// Evaluates an expression that wasn't resolved, looking variables up through
+    /// the current environment chain
+    pub fn evaluate_dynamic(&mut self, expr: &Expr) -> Result<Option<Literal>, RuntimeError> {
+        self.dynamic_scope = true;
+        let value = self.evaluate(expr);
+        self.dynamic_scope = false;
+        return value;
+    }
+
     fn lookup_variable(
         &mut self,
         name: &Token,
@@ -188,6 +641,8 @@ impl Interpreter {
                 *distance,
                 name.lexeme.to_string(),
             )?);
+        } else if self.dynamic_scope {
+            return self.environment.borrow().get(name);
         } else {
             return Ok(self.globals.as_ref().borrow().get(name)?);
         }
@@ -234,6 +689,11 @@ impl ExprVisitor for Interpreter {
     }
 
     fn visit_array(&mut self, values: &Vec<Expr>) -> Self::R {
+        let token = match values.iter().find_map(|value| value.token()) {
+            Some(token) => token.clone(),
+            None => self.line_token(),
+        };
+        self.allocate_elements(values.len(), &token)?;
         let mut qalam_array = QalamArray::init();
         for value in values.iter() {
             qalam_array.elements.push(self.evaluate(value)?);
@@ -330,6 +790,7 @@ impl ExprVisitor for Interpreter {
                     if let (Literal::String(left_val), Literal::String(right_val)) =
                         (left_val, right_val)
                     {
+                        self.allocate(left_val.len() + right_val.len(), operator)?;
                         return Ok(Some(Literal::String(left_val + right_val.as_str())));
                     }
 
@@ -441,6 +902,8 @@ impl ExprVisitor for Interpreter {
         });
         if let Some(distance) = distance {
             Environment::assign_at(self.environment.clone(), *distance, name, res_value.clone())?;
+        } else if self.dynamic_scope {
+            self.environment.borrow_mut().assign(name, res_value.clone())?;
         } else {
             self.globals.borrow_mut().assign(name, res_value.clone())?;
         }
@@ -505,36 +968,15 @@ impl ExprVisitor for Interpreter {
         }
 
         match callee {
-            Some(literal) => {
-                match literal {
-                    Literal::Callable(mut function) => {
-                        if args.len() != function.arity() {
-                            return Err(RuntimeError::init(
-                                paren,
-                                format!(
-                                    "Expected {} arguments but got {}.",
-                                    function.arity(),
-                                    args.len()
-                                ),
-                            ));
-                        }
-                        return Ok(function.call(self, args, paren)?);
-                    }
-                    _ => {
-                        return Err(RuntimeError::init(
-                            paren,
-                            String::from("Can only call functions and classes."),
-                        ));
-                        // throw runtime error, can ONLY call callable
-                    }
-                }
+            Some(Literal::Callable(function)) => {
+                return self.call(function, args, paren);
             }
-            None => {
+            _ => {
                 return Err(RuntimeError::init(
                     paren,
                     String::from("Can only call functions and classes."),
                 ));
-                // throw runtime error, cannot call null
+                // throw runtime error, can ONLY call callable
             }
         }
     }
@@ -546,6 +988,15 @@ impl ExprVisitor for Interpreter {
                 return Ok(QalamInstance::get(object.clone(), name)?);
                 //return Ok(object.0.borrow().get(name)?);
             }
+            if let Some(method) = self.methods.bind(&object, &name.lexeme) {
+                return Ok(Some(Literal::Callable(Box::new(method))));
+            }
+            if let Some(method_type) = MethodType::of(&object) {
+                return Err(RuntimeError::init(
+                    name,
+                    format!("Undefined method '{}' for {}.", name.lexeme, method_type.name()),
+                ));
+            }
         }
 
         return Err(RuntimeError::init(
@@ -713,20 +1164,25 @@ impl StmtVisitor for Interpreter {
     type R = Result<(), RuntimeError>;
     fn visit_expression(&mut self, expression: &Expr) -> Self::R {
         match self.evaluate(expression) {
-            Ok(_) => return Ok(()),
+            Ok(value) => {
+                self.last_value = value;
+                return Ok(());
+            }
             Err(e) => return Err(e),
         }
     }
 
-    fn visit_print(&mut self, expression: &Expr) -> Self::R {
+    fn visit_print(&mut self, keyword: &Token, expression: &Expr) -> Self::R {
         let value = match self.evaluate(expression) {
             Ok(val) => val,
             Err(e) => return Err(e),
         };
-        if let Some(val) = value {
-            println!("{}", val.to_qalam_string());
-        } else {
-            println!("khali")
+        let text = Literal::option_string(value);
+        if writeln!(self.output, "{}", text).is_err() {
+            return Err(RuntimeError::init(
+                keyword,
+                String::from("Failed to write output."),
+            ));
         }
         Ok(())
     }
@@ -816,12 +1272,16 @@ impl StmtVisitor for Interpreter {
         &mut self,
         name: &Token,
         params: &Vec<Token>,
+        defaults: &[Option<Expr>],
+        rest: bool,
         body: &mut Vec<Stmt>,
     ) -> Self::R {
         let function = QalamFunction::init(
             Stmt::Function {
                 name: name.clone(),
                 params: params.clone(),
+                defaults: defaults.to_vec(),
+                rest,
                 body: body.clone(),
             },
             self.environment.clone(),
@@ -835,6 +1295,34 @@ impl StmtVisitor for Interpreter {
     }
 
     fn visit_return(&mut self, _keyword: &Token, value: &Option<Expr>) -> Self::R {
+        if let Some(Expr::Call {
+            callee,
+            paren,
+            arguments,
+        }) = value
+        {
+            let callee = self.evaluate(callee)?;
+            let mut args = Vec::new();
+            for arg in arguments.iter() {
+                args.push(self.evaluate(arg)?);
+            }
+            return match callee {
+                // Tail call, the kaam is called once the current one has returned. Natives,
+                // classes and methods of values don't recurse, so they are called as usual
+                // and the current kaam stays in tracebacks of their errors
+                Some(Literal::Callable(function)) if function.as_any().is::<QalamFunction>() => {
+                    Err(RuntimeError::init_tail_call(function, args, paren))
+                }
+                Some(Literal::Callable(function)) => {
+                    Err(RuntimeError::init_return(self.call(function, args, paren)?))
+                }
+                _ => Err(RuntimeError::init(
+                    paren,
+                    String::from("Can only call functions and classes."),
+                )),
+            };
+        }
+
         let mut val = None;
         match value {
             Some(expr) => val = self.evaluate(expr)?,
@@ -885,13 +1373,9 @@ impl StmtVisitor for Interpreter {
         }
         let mut hash_methods: HashableMap<String, Box<dyn QalamCallable>> = HashableMap::new();
         for method in methods.iter() {
-            if let Stmt::Function { name, params, body } = method {
+            if let Stmt::Function { name, .. } = method {
                 let func = QalamFunction::init(
-                    Stmt::Function {
-                        name: name.clone(),
-                        params: params.clone(),
-                        body: body.clone(),
-                    },
+                    method.clone(),
                     self.environment.clone(),
                     name.lexeme.eq(&String::from("banao")),
                 );
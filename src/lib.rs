@@ -1,56 +1,211 @@
+// Functions end with an explicit `return`, as everywhere else in the interpreter
+#![allow(clippy::needless_return)]
+
 pub mod args;
 pub mod ast;
 pub mod callable;
+pub mod debugger;
 pub mod environment;
 pub mod error;
+pub mod formatter;
+pub mod framing;
 pub mod hashable;
 pub mod interpreter;
+pub mod limits;
+pub mod linter;
+pub mod lsp;
 pub mod literal;
+pub mod methods;
 pub mod native;
+pub mod optimizer;
 pub mod parser;
+pub mod permissions;
+pub mod repl;
 pub mod resolver;
 pub mod scanner;
+pub mod snapshot;
 pub mod stack;
+pub mod symbols;
+pub mod test_runner;
 pub mod token;
 use anyhow::{Context, Result};
-use args::Args;
+use args::{Args, Command};
 use error::{ErrorReporter, ErrorType};
 use interpreter::Interpreter;
+use limits::ExecutionLimits;
+use optimizer::Optimizer;
 use parser::Parser;
+use permissions::{Access, Permissions};
+use ast::printer;
+use ast::stmt::Stmt;
+use serde_json::Value;
+use repl::ReplHelper;
 use resolver::Resolver;
 use scanner::Scanner;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
-use token::Token;
+use std::time::Duration;
+use test_runner::{CapturedOutput, Outcome};
+use colored::Colorize;
+use token::{Token, TokenType};
 use rustyline::error::ReadlineError;
 use rustyline::Editor;
 // use std::cell::RefCell;
 
 pub struct Qalam {
     error_reporter: RefCell<ErrorReporter>,
+    max_call_depth: usize,
+    /// Size of the stack of the thread programs run on
+    stack_size: usize,
+    limits: ExecutionLimits,
+    permissions: Permissions,
+    /// Arguments after the file path, given to the program as `args`
+    script_args: Vec<String>,
+    /// Status the program asked to exit with by calling `exit`
+    exit_code: Cell<Option<i32>>,
+    /// Seed for the random number generator
+    seed: Option<u64>,
+    /// Use a virtual clock and a seeded random number generator
+    deterministic: bool,
+    optimize: bool,
+    show_optimized: bool,
+    show_tokens: bool,
+    show_ast: bool,
+    show_resolved: bool,
+    /// Print what is shown as JSON
+    json: bool,
 }
 
 impl Qalam {
     pub fn init() -> Self {
         return Self {
             error_reporter: RefCell::new(ErrorReporter::init()),
+            max_call_depth: interpreter::DEFAULT_MAX_CALL_DEPTH,
+            stack_size: interpreter::DEFAULT_STACK_SIZE,
+            limits: ExecutionLimits::init(),
+            permissions: Permissions::init(),
+            script_args: Vec::new(),
+            exit_code: Cell::new(None),
+            seed: None,
+            deterministic: false,
+            optimize: false,
+            show_optimized: false,
+            show_tokens: false,
+            show_ast: false,
+            show_resolved: false,
+            json: false,
         };
     }
 
-    fn run_source(&mut self, source: &String, interpreter: Rc<RefCell<Interpreter>>) {
+    /// Sets the size of the stack of the thread `run` is called on, which is
+    /// `interpreter::DEFAULT_STACK_SIZE` unless set
+    pub fn set_stack_size(&mut self, stack_size: usize) {
+        self.stack_size = stack_size;
+    }
+
+    /// Creates an interpreter configured with the options passed to the CLI
+    fn interpreter(&self) -> Rc<RefCell<Interpreter>> {
+        let mut interpreter = Interpreter::init();
+        interpreter.max_call_depth = self.max_call_depth;
+        interpreter.stack_size = self.stack_size;
+        interpreter.limits = self.limits.clone();
+        interpreter.permissions = self.permissions.clone();
+        interpreter.set_args(&self.script_args);
+        if self.deterministic {
+            interpreter.set_deterministic(self.seed.unwrap_or(interpreter::DEFAULT_SEED));
+        } else if let Some(seed) = self.seed {
+            interpreter.seed(seed);
+        }
+        return Rc::new(RefCell::new(interpreter));
+    }
+
+    /// Checks whether the program is only printed (by --tokens, --ast, --resolved
+    /// or --show-optimized) rather than run
+    fn only_showing(&self) -> bool {
+        return self.show_tokens || self.show_ast || self.show_resolved || self.show_optimized;
+    }
+
+    /// Prints a stage of the pipeline, or keeps it to print with the others as one JSON object
+    fn show(&self, sections: &mut serde_json::Map<String, Value>, name: &str, text: String, json: Value) {
+        if self.json {
+            sections.insert(name.to_string(), json);
+        } else {
+            print!("{}", text);
+        }
+    }
+
+    fn run_source(&mut self, source: &str, interpreter: Rc<RefCell<Interpreter>>) {
+        let mut sections = serde_json::Map::new();
+        self.run_stages(source, interpreter, &mut sections);
+        if !sections.is_empty() {
+            println!("{}", serde_json::to_string_pretty(&Value::Object(sections)).unwrap());
+        }
+    }
+
+    fn run_stages(
+        &self,
+        source: &str,
+        interpreter: Rc<RefCell<Interpreter>>,
+        sections: &mut serde_json::Map<String, Value>,
+    ) {
         let mut reporter = self.error_reporter.borrow_mut();
         let mut scanner = Scanner::init(source, &mut reporter);
         let tokens = scanner.scan_tokens();
+        if self.show_tokens {
+            self.show(sections, "tokens", printer::tokens_text(tokens), printer::tokens_json(tokens));
+        }
         let mut parser = Parser::init(tokens);
         match parser.parse() {
             Ok(mut statements) => {
+                if self.show_ast {
+                    self.show(sections, "ast", printer::ast_text(&statements), printer::ast_json(&statements));
+                }
                 let mut resolver = Resolver::init(interpreter.clone());
                 if let Err(e) = resolver.resolve_stmts(&mut statements) {
                     reporter.runtime_error(&e.token, &e.message, ErrorType::Resolution);
                     return;
                 }
-                if let Err(e) = interpreter.clone().borrow_mut().interpret(statements) {
-                    reporter.runtime_error(&e.token, &e.message, ErrorType::Runtime);
+                if self.optimize || self.show_optimized {
+                    // Resolved again so that variables in the optimised AST have scope distances
+                    statements = Optimizer::init().optimize(statements);
+                    if self.show_optimized {
+                        self.show(
+                            sections,
+                            "optimized",
+                            printer::ast_text(&statements),
+                            printer::ast_json(&statements),
+                        );
+                    }
+                    let mut resolver = Resolver::init(interpreter.clone());
+                    if let Err(e) = resolver.resolve_stmts(&mut statements) {
+                        reporter.runtime_error(&e.token, &e.message, ErrorType::Resolution);
+                        return;
+                    }
+                }
+                if self.show_resolved {
+                    let references = printer::references(&statements, &interpreter.borrow());
+                    self.show(
+                        sections,
+                        "resolved",
+                        printer::references_text(&references),
+                        printer::references_json(&references),
+                    );
+                }
+                if self.only_showing() {
+                    return;
+                }
+                let result = interpreter.borrow_mut().interpret(statements);
+                if let Err(e) = result {
+                    if let Some(code) = e.exit_code {
+                        let _ = interpreter.borrow_mut().output.flush();
+                        self.exit_code.set(Some(code));
+                        return;
+                    }
+                    let err_type = match e.limit {
+                        Some(_) => ErrorType::Limit,
+                        None => ErrorType::Runtime,
+                    };
+                    reporter.runtime_error_trace(&e.token, &e.message, &e.trace, err_type);
                 }
             }
             Err(e) => reporter.error_token(&e.token, &e.message, ErrorType::Syntax),
@@ -58,20 +213,60 @@ impl Qalam {
     }
 
     fn run_prompt(&mut self) {
-        let interpreter = Rc::new(RefCell::new(Interpreter::init()));
-        let mut rl= Editor::<(), rustyline::history::FileHistory>::new().expect("Failed to initialize input reader.");
+        let mut interpreter = self.interpreter();
+        interpreter.borrow_mut().echo = true;
+        let mut rl = Editor::<ReplHelper, rustyline::history::FileHistory>::new().expect("Failed to initialize input reader.");
+        let helper = ReplHelper::init(
+            interpreter.borrow().globals.clone(),
+            &interpreter.borrow().methods,
+        );
+        rl.set_helper(Some(helper));
+        let history = repl::history_path();
+        if let Some(history) = history.as_ref() {
+            // There's no history the first time the REPL runs
+            let _ = rl.load_history(history);
+        }
+        // Lines of a statement that isn't finished yet
+        let mut buffer = String::new();
 
         loop {
-            let readline = rl.readline("> ");
+            let prompt = if buffer.is_empty() { "> " } else { "... " };
+            let readline = rl.readline(prompt);
             match readline {
                 Ok(input) => {
-                    let input = input.trim();
-                    if input == "exit()" {
+                    if buffer.is_empty() && input.trim() == "exit()" {
                         break;
                     }
-                    let _ = rl.add_history_entry(input);
-                    self.run_source(&input.to_string(), interpreter.clone());
+                    if buffer.is_empty() && input.trim().is_empty() {
+                        continue;
+                    }
+                    let _ = rl.add_history_entry(input.trim());
+                    if buffer.is_empty() && input.trim().starts_with(':') {
+                        self.run_command(input.trim(), &mut interpreter);
+                        if let Some(helper) = rl.helper_mut() {
+                            helper.globals = interpreter.borrow().globals.clone();
+                        }
+                        continue;
+                    }
+                    buffer.push_str(&input);
+                    buffer.push('\n');
+
+                    // An empty line runs an unfinished statement anyway, to show its error
+                    let source = match Self::complete_input(&buffer) {
+                        Some(source) => source,
+                        None if input.trim().is_empty() => buffer.to_string(),
+                        None => continue,
+                    };
+                    buffer.clear();
+                    self.run_source(&source, interpreter.clone());
                     self.error_reporter.borrow_mut().had_error = false;
+                    if self.exit_code.get().is_some() {
+                        break;
+                    }
+                },
+                Err(ReadlineError::Interrupted) if !buffer.is_empty() => {
+                    // Ctrl-C discards the unfinished statement instead of exiting
+                    buffer.clear();
                 },
                 Err(ReadlineError::Interrupted) => {
                     println!("Exiting...");
@@ -87,12 +282,155 @@ impl Qalam {
                 }
             }
         }
+
+        if let Some(history) = history.as_ref() {
+            if let Err(err) = rl.save_history(history) {
+                eprintln!("Failed to save history to {}: {}", history.display(), err);
+            }
+        }
+        if let Some(code) = self.exit_code.get() {
+            std::process::exit(code);
+        }
+    }
+
+    /// Runs a REPL command, which starts with `:`
+    fn run_command(&mut self, input: &str, interpreter: &mut Rc<RefCell<Interpreter>>) {
+        let (command, argument) = match input.split_once(' ') {
+            Some((command, argument)) => (command, argument.trim()),
+            None => (input, ""),
+        };
+        match command {
+            ":help" => println!("{}", repl::HELP),
+            ":load" => {
+                let source = match std::fs::read_to_string(argument) {
+                    Ok(source) => source,
+                    Err(e) => {
+                        eprintln!("Cannot read file {}: {}", argument, e);
+                        return;
+                    }
+                };
+                // Only values typed at the prompt are echoed
+                interpreter.borrow_mut().echo = false;
+                self.run_source(&source, interpreter.clone());
+                interpreter.borrow_mut().echo = true;
+                self.error_reporter.borrow_mut().had_error = false;
+            }
+            ":reset" => {
+                *interpreter = self.interpreter();
+                interpreter.borrow_mut().echo = true;
+                println!("Session reset");
+            }
+            ":env" => {
+                let globals = interpreter.borrow().globals.clone();
+                for (name, value) in debugger::variables(&globals) {
+                    println!("{}: {}", name, repl::type_name(&value));
+                }
+            }
+            ":type" => match debugger::evaluate(&mut interpreter.borrow_mut(), argument) {
+                Ok(value) => println!("{}", repl::type_name(&value)),
+                Err(message) => eprintln!("Error: {}", message),
+            },
+            ":ast" => {
+                let source = Self::complete_input(argument).unwrap_or(argument.to_string());
+                let mut reporter = self.error_reporter.borrow_mut();
+                let mut scanner = Scanner::init(&source, &mut reporter);
+                let tokens = scanner.scan_tokens();
+                match Parser::init(tokens).parse() {
+                    Ok(statements) => match statements.as_slice() {
+                        [Stmt::Expression { expression }] => print!("{}", printer::expr_text(expression)),
+                        _ => print!("{}", printer::ast_text(&statements)),
+                    },
+                    Err(e) => reporter.error_token(&e.token, &e.message, ErrorType::Syntax),
+                }
+                reporter.had_error = false;
+            }
+            _ => println!("Unknown command '{}'. Type :help for a list of commands.", command),
+        }
+    }
+
+    /// Checks whether REPL input is a finished statement
+    ///
+    /// Input is unfinished while it has unclosed braces, parentheses, brackets or
+    /// strings, or when the parser runs out of tokens. A missing `;` after the
+    /// last statement is added, so `1 + 2` runs as it is.
+    ///
+    /// ### Returns
+    /// `Option<String>` - the source to run, or `None` if more lines are needed
+    fn complete_input(source: &str) -> Option<String> {
+        let mut reporter = ErrorReporter::init_quiet();
+        let mut scanner = Scanner::init(source, &mut reporter);
+        let tokens = scanner.scan_tokens().clone();
+        if reporter.errors.iter().any(|e| e.message == "Unterminated string.") {
+            return None;
+        }
+        let mut depth = 0;
+        for token in tokens.iter() {
+            match token.token_type {
+                TokenType::LeftBrace | TokenType::LeftParen | TokenType::LeftSquare => depth += 1,
+                TokenType::RightBrace | TokenType::RightParen | TokenType::RightSquare => depth -= 1,
+                _ => {}
+            }
+        }
+        if depth > 0 {
+            return None;
+        }
+
+        let parse_error = |source: &str| {
+            let mut reporter = ErrorReporter::init_quiet();
+            let mut scanner = Scanner::init(source, &mut reporter);
+            let tokens = scanner.scan_tokens().clone();
+            return Parser::init(&tokens).parse().err();
+        };
+        let error = match parse_error(source) {
+            Some(error) if error.token.token_type == TokenType::Eof => error,
+            _ => return Some(source.to_string()),
+        };
+        if error.message.starts_with("Expect ';'") {
+            return Some(format!("{};", source.trim_end()));
+        }
+        return None;
     }
 
     fn run_file(&mut self, path: &String) -> Result<()> {
         let file_content =
             std::fs::read_to_string(path).with_context(|| format!("Cannot read file"))?;
-        self.run_source(&file_content, Rc::new(RefCell::new(Interpreter::init())));
+        self.run_source(&file_content, self.interpreter());
+        if let Some(code) = self.exit_code.get() {
+            std::process::exit(code);
+        }
+        if self.error_reporter.borrow().had_error {
+            std::process::exit(65);
+        }
+
+        if self.error_reporter.borrow().had_runtime_error {
+            std::process::exit(75);
+        }
+        return Ok(());
+    }
+
+    /// Runs a file in the console debugger, pausing before the first statement
+    fn debug_file(&mut self, path: &str, breakpoints: &[i64]) -> Result<()> {
+        let is_qlm = std::path::Path::new(path)
+            .extension()
+            .map(|ext| ext == "qlm")
+            .unwrap_or(false);
+        if !is_qlm {
+            return Err(anyhow::anyhow!("Only .qlm files are allowed!"));
+        }
+        let file_content =
+            std::fs::read_to_string(path).context("Cannot read file")?;
+
+        let interpreter = self.interpreter();
+        let console = debugger::console::Console::init(&file_content);
+        interpreter.borrow_mut().debugger = Some(debugger::Debugger::init(
+            Box::new(console),
+            breakpoints.iter().cloned().collect(),
+            true,
+        ));
+        self.run_source(&file_content, interpreter);
+        if let Some(code) = self.exit_code.get() {
+            std::process::exit(code);
+        }
         if self.error_reporter.borrow().had_error {
             std::process::exit(65);
         }
@@ -103,11 +441,324 @@ impl Qalam {
         return Ok(());
     }
 
+    /// Collects the .qlm files at a path, searching directories recursively
+    fn collect_files(path: &std::path::Path, files: &mut Vec<std::path::PathBuf>) -> Result<()> {
+        if path.is_dir() {
+            let mut entries = std::fs::read_dir(path)
+                .with_context(|| format!("Cannot read directory {}", path.display()))?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            entries.sort();
+            for entry in entries.iter() {
+                if entry.is_dir() || entry.extension().map(|ext| ext == "qlm").unwrap_or(false) {
+                    Self::collect_files(entry, files)?;
+                }
+            }
+        } else {
+            files.push(path.to_path_buf());
+        }
+        return Ok(());
+    }
+
+    fn format_files(&mut self, paths: &[String], check: bool) -> Result<()> {
+        let mut files = Vec::new();
+        for path in paths.iter() {
+            Self::collect_files(std::path::Path::new(path), &mut files)?;
+        }
+
+        let mut unformatted = 0;
+        let mut had_error = false;
+        for file in files.iter() {
+            let source = std::fs::read_to_string(file)
+                .with_context(|| format!("Cannot read file {}", file.display()))?;
+            let formatted = match formatter::format_source(&source) {
+                Ok(formatted) => formatted,
+                Err(e) => {
+                    eprintln!("{}: {}", ErrorType::Syntax.to_string(), e.message);
+                    eprintln!("\t at {}:{}", file.display(), e.line);
+                    had_error = true;
+                    continue;
+                }
+            };
+            if formatted == source {
+                continue;
+            }
+            unformatted += 1;
+            if check {
+                println!("Would reformat {}", file.display());
+            } else {
+                std::fs::write(file, formatted)
+                    .with_context(|| format!("Cannot write file {}", file.display()))?;
+                println!("Formatted {}", file.display());
+            }
+        }
+
+        if had_error {
+            std::process::exit(65);
+        }
+        if check && unformatted > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    fn lint_files(&mut self, paths: &[String]) -> Result<()> {
+        let mut files = Vec::new();
+        for path in paths.iter() {
+            Self::collect_files(std::path::Path::new(path), &mut files)?;
+        }
+
+        let mut warnings = 0;
+        let mut had_error = false;
+        for file in files.iter() {
+            let source = std::fs::read_to_string(file)
+                .with_context(|| format!("Cannot read file {}", file.display()))?;
+            match linter::lint_source(&source) {
+                Ok(diagnostics) => {
+                    for d in diagnostics.iter() {
+                        println!(
+                            "{}:{}:{}: warning[{}]: {}",
+                            file.display(),
+                            d.line,
+                            d.column,
+                            d.lint.name(),
+                            d.message
+                        );
+                    }
+                    warnings += diagnostics.len();
+                }
+                Err(e) => {
+                    eprintln!("{}:{}: error: {}", file.display(), e.line, e.message);
+                    had_error = true;
+                }
+            }
+        }
+
+        if had_error {
+            std::process::exit(65);
+        }
+        if warnings > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    fn test_files(&mut self, paths: &[String], filter: Option<&str>) -> Result<()> {
+        let mut files = Vec::new();
+        for path in paths.iter() {
+            let path = std::path::Path::new(path);
+            if path.is_dir() {
+                let mut found = Vec::new();
+                Self::collect_files(path, &mut found)?;
+                files.extend(found.into_iter().filter(|file| {
+                    file.to_string_lossy().ends_with(test_runner::TEST_FILE_SUFFIX)
+                }));
+            } else {
+                files.push(path.to_path_buf());
+            }
+        }
+
+        let mut passed = 0;
+        let mut filtered = 0;
+        let mut failures = Vec::new();
+        let mut had_error = false;
+        for file in files.iter() {
+            let source = std::fs::read_to_string(file)
+                .with_context(|| format!("Cannot read file {}", file.display()))?;
+            let names = match test_runner::test_names(&source) {
+                Ok(names) => names,
+                Err(e) => {
+                    eprintln!("{}:{}: error: {}", file.display(), e.line, e.message);
+                    had_error = true;
+                    continue;
+                }
+            };
+            for name in names.iter() {
+                if filter.map(|f| !name.lexeme.contains(f)).unwrap_or(false) {
+                    filtered += 1;
+                    continue;
+                }
+                let test = format!("{}::{}", file.display(), name.lexeme);
+                match test_runner::run_test(&source, name, self.interpreter()) {
+                    Outcome::Passed => {
+                        println!("test {} ... {}", test, "ok".green());
+                        passed += 1;
+                    }
+                    Outcome::Failed { message, line, output } => {
+                        println!("test {} ... {}", test, "FAILED".red());
+                        failures.push((test, format!("{}:{}", file.display(), line), message, output));
+                    }
+                }
+            }
+        }
+
+        if !failures.is_empty() {
+            println!("\nfailures:");
+            for (test, location, message, output) in failures.iter() {
+                println!("\n---- {} ----", test);
+                print!("{}", output);
+                println!("{}\n\t at {}", message, location);
+            }
+        }
+        let result = match failures.is_empty() && !had_error {
+            true => "ok".green(),
+            false => "FAILED".red(),
+        };
+        println!(
+            "\ntest result: {}. {} passed; {} failed; {} filtered out",
+            result,
+            passed,
+            failures.len(),
+            filtered
+        );
+
+        if had_error {
+            std::process::exit(65);
+        }
+        if !failures.is_empty() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    /// Runs a source the same way as a file, capturing what it prints and its errors together
+    fn run_captured(&mut self, source: &str) -> String {
+        let output = CapturedOutput::init();
+        let mut reporter = ErrorReporter::init();
+        reporter.output = Some(Box::new(output.clone()));
+        self.error_reporter = RefCell::new(reporter);
+        let interpreter = self.interpreter();
+        interpreter.borrow_mut().output = Box::new(output.clone());
+        // Snapshots of programs that use `clock` or random numbers have to be the same each run
+        interpreter
+            .borrow_mut()
+            .set_deterministic(self.seed.unwrap_or(interpreter::DEFAULT_SEED));
+
+        // Colours would end up in the snapshot as escape codes
+        colored::control::set_override(false);
+        self.run_source(source, interpreter);
+        colored::control::unset_override();
+        self.error_reporter = RefCell::new(ErrorReporter::init());
+        return output.contents();
+    }
+
+    fn snapshot_files(&mut self, paths: &[String], update: bool) -> Result<()> {
+        let mut files = Vec::new();
+        for path in paths.iter() {
+            let path = std::path::Path::new(path);
+            if path.is_dir() {
+                // Only programs that have a snapshot are checked in directories
+                let mut found = Vec::new();
+                Self::collect_files(path, &mut found)?;
+                files.extend(
+                    found
+                        .into_iter()
+                        .filter(|file| snapshot::snapshot_path(file).exists()),
+                );
+            } else {
+                files.push(path.to_path_buf());
+            }
+        }
+
+        let mut passed = 0;
+        let mut failed = 0;
+        let mut updated = 0;
+        for file in files.iter() {
+            let source = std::fs::read_to_string(file)
+                .with_context(|| format!("Cannot read file {}", file.display()))?;
+            let actual = self.run_captured(&source);
+            let snapshot = snapshot::snapshot_path(file);
+            let expected = std::fs::read_to_string(&snapshot).ok();
+            if expected.as_ref() == Some(&actual) {
+                println!("snapshot {} ... {}", file.display(), "ok".green());
+                passed += 1;
+                continue;
+            }
+            if update {
+                std::fs::write(&snapshot, &actual)
+                    .with_context(|| format!("Cannot write file {}", snapshot.display()))?;
+                println!("snapshot {} ... {}", file.display(), "updated".yellow());
+                updated += 1;
+                continue;
+            }
+
+            println!("snapshot {} ... {}", file.display(), "FAILED".red());
+            failed += 1;
+            match expected {
+                Some(expected) => {
+                    println!("--- {}\n+++ actual output", snapshot.display());
+                    for line in snapshot::diff(&expected, &actual) {
+                        println!("{}", line);
+                    }
+                }
+                None => println!("No snapshot at {}, run with --update to create it", snapshot.display()),
+            }
+        }
+
+        let result = match failed {
+            0 => "ok".green(),
+            _ => "FAILED".red(),
+        };
+        println!(
+            "\nsnapshot result: {}. {} passed; {} failed; {} updated",
+            result, passed, failed, updated
+        );
+        if failed > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     pub fn run(&mut self, args: Args) -> Result<()> {
+        // Set before the subcommands, so programs run by `test` and `debug` get them too
+        self.max_call_depth = args.max_call_depth;
+        self.script_args = args.script_args.clone();
+        self.seed = args.seed;
+        self.deterministic = args.deterministic;
+        for path in args.allow_read.iter() {
+            self.permissions.allow(Access::Read, path).map_err(anyhow::Error::msg)?;
+        }
+        for path in args.allow_write.iter() {
+            self.permissions.allow(Access::Write, path).map_err(anyhow::Error::msg)?;
+        }
+        self.limits = ExecutionLimits {
+            max_steps: args.max_steps,
+            timeout: args.timeout.map(Duration::from_millis),
+            max_memory: args.max_memory,
+        };
+
+        match &args.command {
+            Some(Command::Fmt { paths, check }) => return self.format_files(paths, *check),
+            Some(Command::Lint { paths }) => return self.lint_files(paths),
+            Some(Command::Lsp) => {
+                let mut server = lsp::LanguageServer::init();
+                let code = server.run(&mut std::io::stdin().lock(), &mut std::io::stdout())?;
+                std::process::exit(code);
+            }
+            Some(Command::Test { paths, filter }) => return self.test_files(paths, filter.as_deref()),
+            Some(Command::Snapshot { paths, update }) => return self.snapshot_files(paths, *update),
+            Some(Command::Debug { file, breakpoints, dap }) => {
+                if *dap {
+                    std::process::exit(debugger::dap::run(self.interpreter())?);
+                }
+                return self.debug_file(file.as_deref().unwrap_or(""), breakpoints);
+            }
+            None => {}
+        }
+
+        self.optimize = args.optimize;
+        self.show_optimized = args.show_optimized;
+        self.show_tokens = args.tokens;
+        self.show_ast = args.ast;
+        self.show_resolved = args.resolved;
+        self.json = args.json;
         if let Some(raw) = args.raw {
             // raw takes priority
             // run the raw string
-            self.run_source(&raw, Rc::new(RefCell::new(Interpreter::init())));
+            self.run_source(&raw, self.interpreter());
+            if let Some(code) = self.exit_code.get() {
+                std::process::exit(code);
+            }
             if self.error_reporter.borrow().had_error {
                 std::process::exit(1);
             }
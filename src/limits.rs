@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+/// Limits on how much work a program is allowed to do
+/// A limit of `None` means there is no limit
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionLimits {
+    /// Maximum number of statements and expressions evaluated
+    pub max_steps: Option<u64>,
+    /// Maximum wall-clock time a program can run for
+    pub timeout: Option<Duration>,
+    /// Maximum number of bytes allocated for arrays and strings
+    pub max_memory: Option<usize>,
+}
+
+impl ExecutionLimits {
+    pub fn init() -> Self {
+        return Self {
+            max_steps: None,
+            timeout: None,
+            max_memory: None,
+        };
+    }
+}
+
+/// Which execution limit was exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitKind {
+    Steps,
+    Timeout,
+    Memory,
+}
+
+impl LimitKind {
+    pub fn message(&self, limits: &ExecutionLimits) -> String {
+        match self {
+            Self::Steps => format!(
+                "Step limit of {} exceeded.",
+                limits.max_steps.unwrap_or_default()
+            ),
+            Self::Timeout => format!(
+                "Time limit of {}ms exceeded.",
+                limits.timeout.unwrap_or_default().as_millis()
+            ),
+            Self::Memory => format!(
+                "Memory limit of {} bytes exceeded.",
+                limits.max_memory.unwrap_or_default()
+            ),
+        }
+    }
+}
@@ -0,0 +1,548 @@
+use crate::ast::expr::Expr;
+use crate::ast::stmt::Stmt;
+use crate::ast::visitor::expr::ExprVisitor;
+use crate::ast::visitor::stmt::StmtVisitor;
+use crate::error::{ErrorReporter, ErrorType, ReportedError};
+use crate::interpreter::Interpreter;
+use crate::literal::Literal;
+use crate::parser::Parser;
+use crate::resolver::Resolver;
+use crate::scanner::Scanner;
+use crate::symbols::{SymbolKind, SymbolTable};
+use crate::test_runner;
+use crate::token::{Token, TokenType};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// Comment prefix used to silence lints, e.g. `// qalam-lint: allow(unused-variable)`
+const ALLOW_PREFIX: &str = "qalam-lint:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    UnusedVariable,
+    UnusedParameter,
+    Shadowing,
+    UnreachableCode,
+    UndeclaredGlobal,
+    NativeArity,
+}
+
+impl Lint {
+    pub fn all() -> [Lint; 6] {
+        return [
+            Lint::UnusedVariable,
+            Lint::UnusedParameter,
+            Lint::Shadowing,
+            Lint::UnreachableCode,
+            Lint::UndeclaredGlobal,
+            Lint::NativeArity,
+        ];
+    }
+
+    /// Name of the lint used in diagnostics and `allow` comments
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Lint::UnusedVariable => "unused-variable",
+            Lint::UnusedParameter => "unused-parameter",
+            Lint::Shadowing => "shadowing",
+            Lint::UnreachableCode => "unreachable-code",
+            Lint::UndeclaredGlobal => "undeclared-global",
+            Lint::NativeArity => "native-arity",
+        };
+    }
+
+    pub fn from_name(name: &str) -> Option<Lint> {
+        return Lint::all().into_iter().find(|lint| lint.name() == name);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub lint: Lint,
+    pub message: String,
+    pub line: i64,
+    pub column: i64,
+}
+
+/// Lints qalam source code
+///
+/// ### Arguments
+/// `source` - the source code to lint
+/// ### Returns
+/// `Result<Vec<Diagnostic>, ReportedError>` - the warnings ordered by position, or the
+/// first syntax or resolution error
+pub fn lint_source(source: &str) -> Result<Vec<Diagnostic>, ReportedError> {
+    let source = source.to_string();
+    let mut reporter = ErrorReporter::init_quiet();
+    let mut scanner = Scanner::init_with_comments(&source, &mut reporter);
+    let tokens = scanner.scan_tokens().clone();
+    if let Some(error) = reporter.errors.first() {
+        return Err(error.clone());
+    }
+
+    let allowed = allowed_lints(&tokens);
+    let tokens: Vec<Token> = tokens
+        .into_iter()
+        .filter(|token| token.token_type != TokenType::Comment)
+        .collect();
+    let mut statements = match Parser::init(&tokens).parse() {
+        Ok(statements) => statements,
+        Err(e) => {
+            reporter.error_token(&e.token, &e.message, ErrorType::Syntax);
+            return Err(reporter.errors[0].clone());
+        }
+    };
+
+    let interpreter = Rc::new(RefCell::new(Interpreter::init()));
+    // Test files use the assertion natives
+    test_runner::add_assertions(&interpreter.borrow());
+    let mut resolver = Resolver::init_with_symbols(interpreter.clone());
+    if let Err(e) = resolver.resolve_stmts(&mut statements) {
+        reporter.runtime_error(&e.token, &e.message, ErrorType::Resolution);
+        return Err(reporter.errors[0].clone());
+    }
+    let symbols = resolver.symbols.take().unwrap();
+
+    let mut natives = HashMap::new();
+    for (name, value) in interpreter.borrow().globals.borrow().iter() {
+        if let Some(Literal::Callable(callable)) = value {
+            natives.insert(name.to_string(), (callable.arity(), callable.max_arity()));
+        }
+    }
+
+    let mut linter = Linter::init(symbols, natives);
+    linter.lint(&mut statements);
+    let mut diagnostics: Vec<Diagnostic> = linter
+        .diagnostics
+        .into_iter()
+        .filter(|d| {
+            let allowed_on = |line: i64| {
+                allowed
+                    .get(&line)
+                    .map(|lints| lints.contains(&d.lint))
+                    .unwrap_or(false)
+            };
+            return !allowed_on(d.line) && !allowed_on(d.line - 1);
+        })
+        .collect();
+    diagnostics.sort_by_key(|d| (d.line, d.column));
+    return Ok(diagnostics);
+}
+
+/// Finds the lints silenced by `allow` comments, keyed by the comment's line
+fn allowed_lints(tokens: &[Token]) -> HashMap<i64, HashSet<Lint>> {
+    let mut allowed: HashMap<i64, HashSet<Lint>> = HashMap::new();
+    for token in tokens.iter() {
+        if token.token_type != TokenType::Comment {
+            continue;
+        }
+        let text = token.lexeme.trim_start_matches('/').trim();
+        let Some(rest) = text.strip_prefix(ALLOW_PREFIX) else {
+            continue;
+        };
+        let Some(list) = rest
+            .trim()
+            .strip_prefix("allow(")
+            .and_then(|rest| rest.split(')').next())
+        else {
+            continue;
+        };
+        let lints = allowed.entry(token.line).or_default();
+        for name in list.split(',') {
+            if let Some(lint) = Lint::from_name(name.trim()) {
+                lints.insert(lint);
+            }
+        }
+    }
+    return allowed;
+}
+
+/// Finds likely mistakes in a resolved program
+///
+/// Scopes aren't tracked here: declarations and uses of names come from the `SymbolTable`
+/// the `Resolver` records, so the linter sees the same bindings the interpreter uses
+pub struct Linter {
+    symbols: SymbolTable,
+    /// Native functions and their least and most number of arguments, `None` if unlimited
+    natives: HashMap<String, (usize, Option<usize>)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Linter {
+    pub fn init(symbols: SymbolTable, natives: HashMap<String, (usize, Option<usize>)>) -> Self {
+        return Self {
+            symbols,
+            natives,
+            diagnostics: Vec::new(),
+        };
+    }
+
+    pub fn lint(&mut self, statements: &mut [Stmt]) {
+        self.lint_declarations();
+        self.lint_references();
+        self.lint_stmts(statements);
+    }
+
+    fn report(&mut self, lint: Lint, token: &Token, message: String) {
+        self.diagnostics.push(Diagnostic {
+            lint,
+            message,
+            line: token.line,
+            column: token.column,
+        });
+    }
+
+    /// Reports locals that shadow another variable or are never read
+    fn lint_declarations(&mut self) {
+        let globals: HashSet<&String> = self
+            .symbols
+            .declarations
+            .iter()
+            .filter(|declaration| declaration.global)
+            .map(|declaration| &declaration.name.lexeme)
+            .collect();
+        // Reported once the declarations are no longer borrowed
+        let mut diagnostics: Vec<(Lint, Token, String)> = Vec::new();
+        for (index, declaration) in self.symbols.declarations.iter().enumerate() {
+            let name = &declaration.name.lexeme;
+            if declaration.global || name.starts_with('_') {
+                continue;
+            }
+
+            if declaration.shadows.is_some() {
+                diagnostics.push((
+                    Lint::Shadowing,
+                    declaration.name.clone(),
+                    format!("'{}' shadows a variable from an outer scope.", name),
+                ));
+            } else if globals.contains(name) {
+                diagnostics.push((
+                    Lint::Shadowing,
+                    declaration.name.clone(),
+                    format!("'{}' shadows a global variable.", name),
+                ));
+            }
+
+            let read = self
+                .symbols
+                .references
+                .iter()
+                .any(|reference| reference.read && reference.declaration == Some(index));
+            if !read {
+                let (lint, message) = match declaration.kind {
+                    SymbolKind::Variable => (
+                        Lint::UnusedVariable,
+                        format!("Local variable '{}' is never used.", name),
+                    ),
+                    SymbolKind::Function => (
+                        Lint::UnusedVariable,
+                        format!("Local kaam '{}' is never used.", name),
+                    ),
+                    SymbolKind::Class => (
+                        Lint::UnusedVariable,
+                        format!("Local jamat '{}' is never used.", name),
+                    ),
+                    SymbolKind::Parameter => (
+                        Lint::UnusedParameter,
+                        format!("Parameter '{}' is never used.", name),
+                    ),
+                };
+                diagnostics.push((lint, declaration.name.clone(), message));
+            }
+        }
+        for (lint, token, message) in diagnostics {
+            self.report(lint, &token, message);
+        }
+    }
+
+    /// Reports uses of names that are neither declared nor natives
+    fn lint_references(&mut self) {
+        let undeclared: Vec<(Token, bool)> = self
+            .symbols
+            .references
+            .iter()
+            .filter(|reference| {
+                reference.declaration.is_none()
+                    && !self.natives.contains_key(&reference.name.lexeme)
+            })
+            .map(|reference| (reference.name.clone(), reference.read))
+            .collect();
+        for (name, read) in undeclared {
+            let message = match read {
+                true => format!("Undefined variable '{}'.", name.lexeme),
+                false => format!("Assignment to undeclared variable '{}'.", name.lexeme),
+            };
+            let message = match self.suggestion(&name.lexeme) {
+                Some(suggestion) => format!("{} Did you mean '{}'?", message, suggestion),
+                None => message,
+            };
+            self.report(Lint::UndeclaredGlobal, &name, message);
+        }
+    }
+
+    /// Lints a list of statements, reporting the first statement after one that always returns
+    fn lint_stmts(&mut self, statements: &mut [Stmt]) {
+        // Token of the statement that always returns
+        let mut returned: Option<Token> = None;
+        let mut reported = false;
+        for stmt in statements.iter_mut() {
+            if let Some(returned) = &returned {
+                if !reported {
+                    // Statements without a token (e.g. `bolo 1;`) are reported at the `wapis`
+                    let token = stmt.token().unwrap_or(returned).clone();
+                    self.report(
+                        Lint::UnreachableCode,
+                        &token,
+                        String::from("Unreachable code after 'wapis'."),
+                    );
+                    reported = true;
+                }
+            } else if Self::always_returns(stmt) {
+                returned = Some(stmt.token().cloned().unwrap_or_else(Token::dummy));
+            }
+            stmt.accept(self);
+        }
+    }
+
+    fn always_returns(stmt: &Stmt) -> bool {
+        return match stmt {
+            Stmt::Return { .. } => true,
+            Stmt::Block { statements } => statements.iter().any(Self::always_returns),
+            Stmt::If {
+                then,
+                else_branch: Some(else_branch),
+                ..
+            } => Self::always_returns(then) && Self::always_returns(else_branch),
+            _ => false,
+        };
+    }
+
+    fn lint_expr(&mut self, expr: &Expr) {
+        expr.accept(self);
+    }
+
+    /// Finds the declaration a use of a name refers to, `None` for natives and undeclared names
+    fn declaration_of(&self, name: &Token) -> Option<usize> {
+        return self
+            .symbols
+            .references
+            .iter()
+            .find(|reference| {
+                reference.name.line == name.line && reference.name.column == name.column
+            })
+            .and_then(|reference| reference.declaration);
+    }
+
+    /// Suggests a declared name that is close to a misspelt one
+    fn suggestion(&self, name: &str) -> Option<String> {
+        return self
+            .symbols
+            .declarations
+            .iter()
+            .map(|declaration| &declaration.name.lexeme)
+            .chain(self.natives.keys())
+            .map(|candidate| (edit_distance(name, candidate), candidate))
+            .filter(|(distance, candidate)| *distance <= 2 && *distance < candidate.chars().count())
+            .min()
+            .map(|(_, candidate)| candidate.to_string());
+    }
+
+    fn lint_function(&mut self, defaults: &[Option<Expr>], body: &mut [Stmt]) {
+        for default in defaults.iter().flatten() {
+            self.lint_expr(default);
+        }
+        self.lint_stmts(body);
+    }
+}
+
+/// Number of single character edits needed to turn one string into another
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    return previous[b.len()];
+}
+
+impl StmtVisitor for Linter {
+    type R = ();
+
+    fn visit_block(&mut self, statements: &mut Vec<Stmt>) -> Self::R {
+        self.lint_stmts(statements);
+    }
+
+    fn visit_expression(&mut self, expression: &Expr) -> Self::R {
+        self.lint_expr(expression);
+    }
+
+    fn visit_function(
+        &mut self,
+        _name: &Token,
+        _params: &Vec<Token>,
+        defaults: &[Option<Expr>],
+        _rest: bool,
+        body: &mut Vec<Stmt>,
+    ) -> Self::R {
+        self.lint_function(defaults, body);
+    }
+
+    fn visit_if(
+        &mut self,
+        condition: &Expr,
+        then: &mut Box<Stmt>,
+        else_branch: &mut Option<Box<Stmt>>,
+    ) -> Self::R {
+        self.lint_expr(condition);
+        then.accept(self);
+        if let Some(else_branch) = else_branch {
+            else_branch.accept(self);
+        }
+    }
+
+    fn visit_print(&mut self, _keyword: &Token, expression: &Expr) -> Self::R {
+        self.lint_expr(expression);
+    }
+
+    fn visit_return(&mut self, _keyword: &Token, value: &Option<Expr>) -> Self::R {
+        if let Some(value) = value {
+            self.lint_expr(value);
+        }
+    }
+
+    fn visit_var(&mut self, _name: &Token, initializer: &Option<Expr>) -> Self::R {
+        if let Some(initializer) = initializer {
+            self.lint_expr(initializer);
+        }
+    }
+
+    fn visit_while(&mut self, condition: &Expr, body: &mut Box<Stmt>) -> Self::R {
+        self.lint_expr(condition);
+        body.accept(self);
+    }
+
+    fn visit_class(
+        &mut self,
+        _name: &Token,
+        methods: &mut Vec<Stmt>,
+        superclass: &Option<Expr>,
+    ) -> Self::R {
+        if let Some(superclass) = superclass {
+            self.lint_expr(superclass);
+        }
+        for method in methods.iter_mut() {
+            if let Stmt::Function { defaults, body, .. } = method {
+                self.lint_function(defaults, body);
+            }
+        }
+    }
+}
+
+impl ExprVisitor for Linter {
+    type R = ();
+
+    fn visit_assign(&mut self, _name: &Token, value: &Box<Expr>) -> Self::R {
+        self.lint_expr(value);
+    }
+
+    fn visit_binary(&mut self, left: &Box<Expr>, _operator: &Token, right: &Box<Expr>) -> Self::R {
+        self.lint_expr(left);
+        self.lint_expr(right);
+    }
+
+    fn visit_call(&mut self, callee: &Box<Expr>, paren: &Token, arguments: &Vec<Expr>) -> Self::R {
+        self.lint_expr(callee);
+        for arg in arguments.iter() {
+            self.lint_expr(arg);
+        }
+
+        // Only natives that haven't been redeclared by the program are checked
+        if let Expr::Variable { name } = callee.as_ref() {
+            if self.declaration_of(name).is_some() {
+                return;
+            }
+            if let Some((arity, max_arity)) = self.natives.get(&name.lexeme).copied() {
+                let count = arguments.len();
+                let expected = match max_arity {
+                    Some(max_arity) if max_arity == arity => arity.to_string(),
+                    Some(max_arity) => format!("{} to {}", arity, max_arity),
+                    None => format!("at least {}", arity),
+                };
+                if count < arity || max_arity.is_some_and(|max_arity| count > max_arity) {
+                    self.report(
+                        Lint::NativeArity,
+                        paren,
+                        format!(
+                            "'{}' expects {} argument(s) but got {}.",
+                            name.lexeme, expected, count
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn visit_grouping(&mut self, expression: &Box<Expr>) -> Self::R {
+        self.lint_expr(expression);
+    }
+
+    fn visit_literal(&mut self, _expr: &Option<Literal>) -> Self::R {}
+
+    fn visit_array(&mut self, values: &Vec<Expr>) -> Self::R {
+        for value in values.iter() {
+            self.lint_expr(value);
+        }
+    }
+
+    fn visit_logical(&mut self, left: &Box<Expr>, _operator: &Token, right: &Box<Expr>) -> Self::R {
+        self.lint_expr(left);
+        self.lint_expr(right);
+    }
+
+    fn visit_unary(&mut self, _operator: &Token, right: &Box<Expr>) -> Self::R {
+        self.lint_expr(right);
+    }
+
+    fn visit_variable(&mut self, _name: &Token) -> Self::R {}
+
+    fn visit_get(&mut self, object: &Box<Expr>, _name: &Token) -> Self::R {
+        self.lint_expr(object);
+    }
+
+    fn visit_set(&mut self, object: &Box<Expr>, _name: &Token, value: &Box<Expr>) -> Self::R {
+        self.lint_expr(value);
+        self.lint_expr(object);
+    }
+
+    fn visit_this(&mut self, _keyword: &Token) -> Self::R {}
+
+    fn visit_super(&mut self, _keyword: &Token, _method: &Token) -> Self::R {}
+
+    fn visit_get_indexed(
+        &mut self,
+        object: &Box<Expr>,
+        index: &Box<Expr>,
+        _bracket: &Token,
+    ) -> Self::R {
+        self.lint_expr(object);
+        self.lint_expr(index);
+    }
+
+    fn visit_set_indexed(
+        &mut self,
+        object: &Box<Expr>,
+        index: &Box<Expr>,
+        value: &Box<Expr>,
+        _bracket: &Token,
+    ) -> Self::R {
+        self.lint_expr(object);
+        self.lint_expr(index);
+        self.lint_expr(value);
+    }
+}
@@ -3,6 +3,8 @@ use crate::callable::QalamCallable;
 use crate::hashable::HashableRcRefCell;
 use ordered_float::OrderedFloat;
 use colored::Colorize;
+use std::collections::HashSet;
+use std::rc::Rc;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct QalamArray {
@@ -53,7 +55,7 @@ impl PartialEq for Literal {
             (Literal::Number(a), Literal::Number(b)) => a == b,
             (Literal::String(a), Literal::String(b)) => a == b,
             (Literal::Bool(a), Literal::Bool(b)) => a == b,
-            (Literal::Callable(a), Literal::Callable(b)) => std::ptr::eq(&**a, &**b),
+            (Literal::Callable(a), Literal::Callable(b)) => a == b,
             (Literal::Array(a), Literal::Array(b)) => a == b,
             (Literal::Instance(a), Literal::Instance(b)) => a == b,
             _ => false,
@@ -79,4 +81,65 @@ impl Literal {
             None => String::from("khali"),
         }
     }
+
+    /// Name of the type of a value, as returned by `typeof`
+    pub fn type_name(value: &Option<Literal>) -> String {
+        match value {
+            Some(Literal::Bool(_)) => String::from("bool"),
+            Some(Literal::Number(_)) => String::from("number"),
+            Some(Literal::String(_)) => String::from("string"),
+            Some(Literal::Callable(_)) => String::from("amal"),
+            Some(Literal::Instance(instance)) => instance.0.borrow().to_string(),
+            Some(Literal::Array(_)) => String::from("array"),
+            None => String::from("ghaib"),
+        }
+    }
+
+    /// Checks if two values are structurally equal
+    /// Arrays are compared element by element and instances field by field.
+    /// Cycles are handled by treating a pair that is already being compared as equal.
+    pub fn deep_equal(a: &Option<Literal>, b: &Option<Literal>) -> bool {
+        return Self::deep_equal_visited(a, b, &mut HashSet::new());
+    }
+
+    fn deep_equal_visited(
+        a: &Option<Literal>,
+        b: &Option<Literal>,
+        visited: &mut HashSet<(usize, usize)>,
+    ) -> bool {
+        match (a, b) {
+            (Some(Literal::Array(a)), Some(Literal::Array(b))) => {
+                let pair = (Rc::as_ptr(&a.0) as usize, Rc::as_ptr(&b.0) as usize);
+                if !visited.insert(pair) {
+                    return true;
+                }
+                let a = a.0.borrow();
+                let b = b.0.borrow();
+                if a.elements.len() != b.elements.len() {
+                    return false;
+                }
+                return a
+                    .elements
+                    .iter()
+                    .zip(b.elements.iter())
+                    .all(|(a, b)| Self::deep_equal_visited(a, b, visited));
+            }
+            (Some(Literal::Instance(a)), Some(Literal::Instance(b))) => {
+                let pair = (Rc::as_ptr(&a.0) as usize, Rc::as_ptr(&b.0) as usize);
+                if !visited.insert(pair) {
+                    return true;
+                }
+                let a = a.0.borrow();
+                let b = b.0.borrow();
+                if a.class != b.class || a.fields.len() != b.fields.len() {
+                    return false;
+                }
+                return a.fields.iter().all(|(name, a_val)| match b.fields.get(name) {
+                    Some(b_val) => Self::deep_equal_visited(a_val, b_val, visited),
+                    None => false,
+                });
+            }
+            _ => return a == b,
+        }
+    }
 }
@@ -0,0 +1,230 @@
+use crate::error::ErrorReporter;
+use crate::interpreter::Interpreter;
+use crate::linter;
+use crate::literal::Literal;
+use crate::parser::Parser;
+use crate::resolver::Resolver;
+use crate::scanner::Scanner;
+use crate::symbols::SymbolTable;
+use crate::token::{Token, TokenType, KEYWORDS};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem found in a document
+#[derive(Debug, Clone)]
+pub struct Problem {
+    pub severity: Severity,
+    pub message: String,
+    pub line: i64,
+    pub column: i64,
+    pub length: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompletionKind {
+    Keyword,
+    Function,
+    Class,
+    Variable,
+}
+
+/// Everything the language server knows about one version of a document
+pub struct Analysis {
+    pub problems: Vec<Problem>,
+    tokens: Vec<Token>,
+    /// `None` when the document doesn't parse
+    symbols: Option<SymbolTable>,
+    /// Native functions and their `to_string`
+    natives: HashMap<String, String>,
+    /// Globals of the interpreter that aren't functions, like `math` and `args`, sorted
+    native_values: Vec<String>,
+}
+
+impl Analysis {
+    pub fn analyze(source: &str) -> Self {
+        let source = source.to_string();
+        let mut natives = HashMap::new();
+        let mut native_values = Vec::new();
+        for (name, value) in Interpreter::init().globals.borrow().iter() {
+            match value {
+                Some(Literal::Callable(callable)) => {
+                    natives.insert(name.to_string(), callable.to_string());
+                }
+                _ => native_values.push(name.to_string()),
+            }
+        }
+        native_values.sort();
+
+        let mut reporter = ErrorReporter::init_quiet();
+        let mut scanner = Scanner::init(&source, &mut reporter);
+        let tokens = scanner.scan_tokens().clone();
+        let mut analysis = Self {
+            problems: Vec::new(),
+            tokens,
+            symbols: None,
+            natives,
+            native_values,
+        };
+        if !reporter.errors.is_empty() {
+            let lines: Vec<&str> = source.lines().collect();
+            for error in reporter.errors.iter() {
+                let length = lines
+                    .get((error.line - 1).max(0) as usize)
+                    .map(|line| line.chars().count())
+                    .unwrap_or(0);
+                analysis.error(error.line, 1, length as i64, &error.message);
+            }
+            return analysis;
+        }
+
+        let mut statements = match Parser::init(&analysis.tokens).parse() {
+            Ok(statements) => statements,
+            Err(e) => {
+                analysis.error_token(&e.token, &e.message);
+                return analysis;
+            }
+        };
+
+        let mut resolver = Resolver::init_with_symbols(Rc::new(RefCell::new(Interpreter::init())));
+        if let Err(e) = resolver.resolve_stmts(&mut statements) {
+            analysis.error_token(&e.token, &e.message);
+            return analysis;
+        }
+        analysis.symbols = resolver.symbols.take();
+
+        if let Ok(diagnostics) = linter::lint_source(&source) {
+            for d in diagnostics.iter() {
+                let length = analysis
+                    .token_at(d.line, d.column)
+                    .map(|token| token.lexeme.chars().count() as i64)
+                    .unwrap_or(1);
+                analysis.problems.push(Problem {
+                    severity: Severity::Warning,
+                    message: format!("{} [{}]", d.message, d.lint.name()),
+                    line: d.line,
+                    column: d.column,
+                    length,
+                });
+            }
+        }
+        return analysis;
+    }
+
+    fn error(&mut self, line: i64, column: i64, length: i64, message: &str) {
+        self.problems.push(Problem {
+            severity: Severity::Error,
+            message: message.to_string(),
+            line,
+            column,
+            length,
+        });
+    }
+
+    fn error_token(&mut self, token: &Token, message: &str) {
+        let length = token.lexeme.chars().count() as i64;
+        self.error(token.line, token.column, length, message);
+    }
+
+    /// Finds the token covering a position
+    ///
+    /// ### Arguments
+    /// `line` - line of the position (starting at 1)
+    /// `column` - column of the position (starting at 1)
+    pub fn token_at(&self, line: i64, column: i64) -> Option<&Token> {
+        return self.tokens.iter().find(|token| {
+            token.line == line
+                && column >= token.column
+                && column <= token.column + token.lexeme.chars().count() as i64
+                && token.token_type != TokenType::Eof
+        });
+    }
+
+    /// Finds the declaration of the name at a position
+    pub fn definition(&self, line: i64, column: i64) -> Option<&Token> {
+        let symbols = self.symbols.as_ref()?;
+        let index = symbols.declaration_at(line, column)?;
+        return Some(&symbols.declarations[index].name);
+    }
+
+    /// Finds every use of the name at a position
+    pub fn references(&self, line: i64, column: i64, include_declaration: bool) -> Vec<&Token> {
+        let Some(symbols) = self.symbols.as_ref() else {
+            return Vec::new();
+        };
+        let Some(index) = symbols.declaration_at(line, column) else {
+            return Vec::new();
+        };
+        let mut tokens = Vec::new();
+        if include_declaration {
+            tokens.push(&symbols.declarations[index].name);
+        }
+        tokens.extend(symbols.references_to(index));
+        return tokens;
+    }
+
+    /// Describes the name at a position
+    pub fn hover(&self, line: i64, column: i64) -> Option<String> {
+        let token = self.token_at(line, column)?;
+        if token.token_type != TokenType::Identifier {
+            return None;
+        }
+        if let Some(symbols) = self.symbols.as_ref() {
+            if let Some(index) = symbols.declaration_at(line, column) {
+                return Some(symbols.declarations[index].signature());
+            }
+        }
+        // Names that don't resolve to a declaration are globals, which may be natives
+        return self.natives.get(&token.lexeme).map(|native| {
+            let signature = native
+                .trim_start_matches("<native amal ")
+                .trim_end_matches('>');
+            return format!("{} (native amal)", signature);
+        });
+    }
+
+    /// Lists keywords, the interpreter's globals and globals declared in the document
+    pub fn completions(&self) -> Vec<(String, CompletionKind)> {
+        let mut items: Vec<(String, CompletionKind)> = KEYWORDS
+            .iter()
+            .map(|keyword| (keyword.to_string(), CompletionKind::Keyword))
+            .collect();
+        let mut natives: Vec<&String> = self.natives.keys().collect();
+        natives.sort();
+        for native in natives {
+            items.push((native.to_string(), CompletionKind::Function));
+        }
+        for value in self.native_values.iter() {
+            items.push((value.to_string(), CompletionKind::Variable));
+        }
+
+        // Globals are found from the tokens so that completion works while the document doesn't parse
+        let mut depth = 0;
+        for pair in self.tokens.windows(2) {
+            match pair[0].token_type {
+                TokenType::LeftBrace => depth += 1,
+                TokenType::RightBrace => depth -= 1,
+                _ => {}
+            }
+            if depth != 0 || pair[1].token_type != TokenType::Identifier {
+                continue;
+            }
+            let kind = match pair[0].token_type {
+                TokenType::Var => CompletionKind::Variable,
+                TokenType::Fun => CompletionKind::Function,
+                TokenType::Class => CompletionKind::Class,
+                _ => continue,
+            };
+            if !items.iter().any(|(label, _)| *label == pair[1].lexeme) {
+                items.push((pair[1].lexeme.to_string(), kind));
+            }
+        }
+        return items;
+    }
+}
@@ -0,0 +1,284 @@
+pub mod analysis;
+pub mod position;
+use crate::framing::{read_frame, write_framed_message};
+use analysis::{Analysis, CompletionKind, Severity};
+use anyhow::Result;
+use position::PositionEncoding;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+// JSON-RPC error codes for messages that aren't JSON and requests the server doesn't support
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Language server speaking the Language Server Protocol over stdio
+///
+/// Documents are synced in full on every change. Positions are converted between the
+/// protocol's 0-based lines and characters and the scanner's 1-based lines and columns,
+/// counting characters in the `PositionEncoding` agreed with the client.
+pub struct LanguageServer {
+    documents: HashMap<String, Document>,
+    encoding: PositionEncoding,
+    shutdown_requested: bool,
+}
+
+/// An open document with what the server knows about its current version
+struct Document {
+    lines: Vec<String>,
+    analysis: Analysis,
+}
+
+impl Document {
+    /// Text of a 1-based line, empty past the end of the document
+    fn line(&self, line: i64) -> &str {
+        return self
+            .lines
+            .get((line - 1).max(0) as usize)
+            .map(|line| line.as_str())
+            .unwrap_or("");
+    }
+}
+
+impl LanguageServer {
+    pub fn init() -> Self {
+        return Self {
+            documents: HashMap::new(),
+            encoding: PositionEncoding::Utf16,
+            shutdown_requested: false,
+        };
+    }
+
+    /// Serves requests until the client sends `exit` or closes the input
+    ///
+    /// ### Returns
+    /// `Result<i32>` - the exit code, which is `1` if the client exits without a `shutdown` request
+    pub fn run<R: BufRead, W: Write>(&mut self, input: &mut R, output: &mut W) -> Result<i32> {
+        while let Some(body) = read_frame(input)? {
+            // The id of a message that doesn't parse is unknown, so the error has a null id
+            let message: Value = match serde_json::from_slice(&body) {
+                Ok(message) => message,
+                Err(e) => {
+                    let error = json!({
+                        "jsonrpc": "2.0",
+                        "id": Value::Null,
+                        "error": { "code": PARSE_ERROR, "message": format!("Parse error: {}", e) }
+                    });
+                    write_framed_message(output, &error)?;
+                    continue;
+                }
+            };
+            if message["method"] == "exit" {
+                return Ok(if self.shutdown_requested { 0 } else { 1 });
+            }
+            for response in self.handle(&message) {
+                write_framed_message(output, &response)?;
+            }
+        }
+        return Ok(0);
+    }
+
+    /// Handles a message from the client
+    ///
+    /// ### Returns
+    /// `Vec<Value>` - the messages to send back, a response for requests and
+    /// diagnostics for changed documents
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let method = message["method"].as_str().unwrap_or("");
+        let params = &message["params"];
+        let id = message.get("id").cloned();
+
+        let result = match method {
+            "initialize" => {
+                let offered = params["capabilities"]["general"]["positionEncodings"].as_array();
+                self.encoding = PositionEncoding::negotiate(offered);
+                Some(json!({
+                    "capabilities": {
+                        "positionEncoding": self.encoding.name(),
+                        "textDocumentSync": 1,
+                        "hoverProvider": true,
+                        "definitionProvider": true,
+                        "referencesProvider": true,
+                        "completionProvider": {}
+                    },
+                    "serverInfo": {
+                        "name": "qalam",
+                        "version": env!("CARGO_PKG_VERSION")
+                    }
+                }))
+            }
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Some(Value::Null)
+            }
+            "textDocument/didOpen" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or("");
+                let text = params["textDocument"]["text"].as_str().unwrap_or("");
+                return vec![self.update(uri, text)];
+            }
+            "textDocument/didChange" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or("");
+                let changes = params["contentChanges"].as_array();
+                return match changes.and_then(|changes| changes.last()) {
+                    Some(change) => vec![self.update(uri, change["text"].as_str().unwrap_or(""))],
+                    None => Vec::new(),
+                };
+            }
+            "textDocument/didClose" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or("");
+                self.documents.remove(uri);
+                return vec![Self::diagnostics(uri, Vec::new())];
+            }
+            "textDocument/definition" => Some(self.definition(params)),
+            "textDocument/references" => Some(self.references(params)),
+            "textDocument/hover" => Some(self.hover(params)),
+            "textDocument/completion" => Some(self.completion(params)),
+            _ => None,
+        };
+
+        // Notifications (messages without an id) never get a response
+        let Some(id) = id else {
+            return Vec::new();
+        };
+        return match result {
+            Some(result) => vec![json!({ "jsonrpc": "2.0", "id": id, "result": result })],
+            None => vec![json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {
+                    "code": METHOD_NOT_FOUND,
+                    "message": format!("Method '{}' is not supported.", method)
+                }
+            })],
+        };
+    }
+
+    /// Analyses a document and creates the notification publishing its diagnostics
+    fn update(&mut self, uri: &str, text: &str) -> Value {
+        let document = Document {
+            lines: text.lines().map(|line| line.to_string()).collect(),
+            analysis: Analysis::analyze(text),
+        };
+        let diagnostics = document
+            .analysis
+            .problems
+            .iter()
+            .map(|problem| {
+                let line = document.line(problem.line);
+                let start = json!({
+                    "line": problem.line - 1,
+                    "character": self.encoding.character(line, problem.column)
+                });
+                let end = json!({
+                    "line": problem.line - 1,
+                    "character": self.encoding.character(line, problem.column + problem.length)
+                });
+                let severity = match problem.severity {
+                    Severity::Error => 1,
+                    Severity::Warning => 2,
+                };
+                return json!({
+                    "range": { "start": start, "end": end },
+                    "severity": severity,
+                    "source": "qalam",
+                    "message": problem.message
+                });
+            })
+            .collect();
+        self.documents.insert(uri.to_string(), document);
+        return Self::diagnostics(uri, diagnostics);
+    }
+
+    fn diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+        return json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics }
+        });
+    }
+
+    /// Finds the document and 1-based line and column a request is about
+    fn position<'a>(&'a self, params: &Value) -> Option<(&'a Document, &'a str, i64, i64)> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        let (uri, document) = self.documents.get_key_value(uri)?;
+        let line = params["position"]["line"].as_i64()? + 1;
+        let character = params["position"]["character"].as_i64()?;
+        let column = self.encoding.column(document.line(line), character);
+        return Some((document, uri.as_str(), line, column));
+    }
+
+    fn location(&self, document: &Document, uri: &str, token: &crate::token::Token) -> Value {
+        let line = document.line(token.line);
+        let end = token.column + token.lexeme.chars().count() as i64;
+        return json!({
+            "uri": uri,
+            "range": {
+                "start": {
+                    "line": token.line - 1,
+                    "character": self.encoding.character(line, token.column)
+                },
+                "end": { "line": token.line - 1, "character": self.encoding.character(line, end) }
+            }
+        });
+    }
+
+    fn definition(&self, params: &Value) -> Value {
+        let Some((document, uri, line, column)) = self.position(params) else {
+            return Value::Null;
+        };
+        return match document.analysis.definition(line, column) {
+            Some(token) => self.location(document, uri, token),
+            None => Value::Null,
+        };
+    }
+
+    fn references(&self, params: &Value) -> Value {
+        let Some((document, uri, line, column)) = self.position(params) else {
+            return json!([]);
+        };
+        let include_declaration = params["context"]["includeDeclaration"]
+            .as_bool()
+            .unwrap_or(true);
+        let locations: Vec<Value> = document
+            .analysis
+            .references(line, column, include_declaration)
+            .into_iter()
+            .map(|token| self.location(document, uri, token))
+            .collect();
+        return json!(locations);
+    }
+
+    fn hover(&self, params: &Value) -> Value {
+        let Some((document, _, line, column)) = self.position(params) else {
+            return Value::Null;
+        };
+        return match document.analysis.hover(line, column) {
+            Some(text) => json!({
+                "contents": { "kind": "markdown", "value": format!("```qalam\n{}\n```", text) }
+            }),
+            None => Value::Null,
+        };
+    }
+
+    fn completion(&self, params: &Value) -> Value {
+        let Some((document, _, _, _)) = self.position(params) else {
+            return json!([]);
+        };
+        let items: Vec<Value> = document
+            .analysis
+            .completions()
+            .into_iter()
+            .map(|(label, kind)| {
+                // Completion item kinds from the specification
+                let kind = match kind {
+                    CompletionKind::Function => 3,
+                    CompletionKind::Variable => 6,
+                    CompletionKind::Class => 7,
+                    CompletionKind::Keyword => 14,
+                };
+                return json!({ "label": label, "kind": kind });
+            })
+            .collect();
+        return json!(items);
+    }
+}
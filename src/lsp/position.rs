@@ -0,0 +1,58 @@
+/// How the `character` of a position counts along a line, agreed with the client in `initialize`
+///
+/// The scanner counts columns in chars, which is UTF-32. Clients that don't offer
+/// `utf-32` get the protocol's default UTF-16, where a char outside the Basic
+/// Multilingual Plane (e.g. an emoji) takes two code units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PositionEncoding {
+    Utf16,
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// Picks an encoding from the `positionEncodings` the client offers
+    pub fn negotiate(offered: Option<&Vec<serde_json::Value>>) -> Self {
+        let utf32 = offered.is_some_and(|offered| offered.iter().any(|e| e == "utf-32"));
+        return if utf32 { Self::Utf32 } else { Self::Utf16 };
+    }
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Self::Utf16 => "utf-16",
+            Self::Utf32 => "utf-32",
+        };
+    }
+
+    /// Converts a 1-based column of `line` to the 0-based character of a protocol position
+    pub fn character(&self, line: &str, column: i64) -> i64 {
+        let chars = (column - 1).max(0);
+        return match self {
+            Self::Utf32 => chars,
+            Self::Utf16 => {
+                let units: usize = line.chars().take(chars as usize).map(char::len_utf16).sum();
+                // Columns past the end of the line count one unit each
+                let past_end = chars - line.chars().count() as i64;
+                units as i64 + past_end.max(0)
+            }
+        };
+    }
+
+    /// Converts the 0-based character of a protocol position on `line` to a 1-based column
+    pub fn column(&self, line: &str, character: i64) -> i64 {
+        return match self {
+            Self::Utf32 => character + 1,
+            Self::Utf16 => {
+                let mut units = 0;
+                let mut column = 1;
+                for c in line.chars() {
+                    if units >= character {
+                        return column;
+                    }
+                    units += c.len_utf16() as i64;
+                    column += 1;
+                }
+                column + (character - units).max(0)
+            }
+        };
+    }
+}
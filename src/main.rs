@@ -3,8 +3,22 @@ use clap::Parser;
 use qalam::args::Args;
 use qalam::Qalam;
 
+// Every qalam call nests several Rust calls, so the interpreter runs on a thread
+// with a stack large enough to reach the maximum call depth
+const INTERPRETER_STACK_SIZE: usize = 256 * 1024 * 1024;
+
 fn main() -> Result<()> {
     let args = Args::parse();
-    let mut qalam = Qalam::init();
-    return qalam.run(args);
+    let handle = std::thread::Builder::new()
+        .name(String::from("qalam"))
+        .stack_size(INTERPRETER_STACK_SIZE)
+        .spawn(move || {
+            let mut qalam = Qalam::init();
+            qalam.set_stack_size(INTERPRETER_STACK_SIZE);
+            return qalam.run(args);
+        })?;
+    return match handle.join() {
+        Ok(result) => result,
+        Err(_) => Err(anyhow::anyhow!("Interpreter thread panicked!")),
+    };
 }
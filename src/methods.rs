@@ -0,0 +1,194 @@
+use crate::callable::QalamCallable;
+use crate::error::RuntimeError;
+use crate::interpreter::Interpreter;
+use crate::literal::Literal;
+use crate::token::Token;
+use std::collections::HashMap;
+
+/// Globals that strings also have as methods, e.g. `"abc".len()` calls `len("abc")`
+/// Natives that only work on strings are methods without being globals, see `Interpreter::add_methods`
+pub const STRING_METHODS: [&str; 8] = [
+    "len", "format", "index_of", "replace", "substr", "code", "str2num", "str",
+];
+
+/// Globals that arrays also have as methods, e.g. `arr.push(1)` calls `push(arr, 1)`
+pub const ARRAY_METHODS: [&str; 6] = ["len", "push", "pop", "index_of", "slice", "str"];
+
+/// Natives that numbers have as methods, e.g. `x.floor()` calls `floor(x)`
+pub const NUMBER_METHODS: [&str; 6] = ["floor", "ceil", "round", "max", "min", "str"];
+
+/// Functions of the `math` namespace that numbers have as methods, e.g. `x.sqrt()`
+pub const NUMBER_MATH_METHODS: [&str; 12] = [
+    "sqrt", "abs", "sin", "cos", "tan", "exp", "log", "log10", "log2", "sign", "trunc", "clamp",
+];
+
+/// Built-in types that can have methods
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MethodType {
+    String,
+    Array,
+    Number,
+}
+
+impl MethodType {
+    /// Finds the type of a value, if it is one that can have methods
+    pub fn of(value: &Literal) -> Option<Self> {
+        return match value {
+            Literal::String(_) => Some(Self::String),
+            Literal::Array(_) => Some(Self::Array),
+            Literal::Number(_) => Some(Self::Number),
+            _ => None,
+        };
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Number => "number",
+        }
+    }
+}
+
+/// Registry of the methods of built-in types
+/// A method is a callable that takes the value it's called on as its first argument
+#[derive(Debug, Clone)]
+pub struct Methods {
+    methods: HashMap<(MethodType, String), Box<dyn QalamCallable>>,
+}
+
+impl Methods {
+    pub fn init() -> Self {
+        return Self {
+            methods: HashMap::new(),
+        };
+    }
+
+    /// Adds a method to a type, replacing any method with the same name
+    pub fn add<F>(&mut self, method_type: MethodType, name: &str, method: F)
+    where
+        F: QalamCallable + 'static,
+    {
+        self.methods
+            .insert((method_type, name.to_string()), Box::new(method));
+    }
+
+    /// Adds callables from a set of values (e.g. the globals) as methods of a type
+    pub fn add_from<'a, I>(&mut self, method_type: MethodType, names: &[&str], values: I)
+    where
+        I: Iterator<Item = (&'a String, &'a Option<Literal>)>,
+    {
+        for (name, value) in values {
+            if !names.contains(&name.as_str()) {
+                continue;
+            }
+            if let Some(Literal::Callable(callable)) = value {
+                self.methods
+                    .insert((method_type, name.to_string()), callable.clone());
+            }
+        }
+    }
+
+    /// Finds a method of a value and binds the value to it
+    ///
+    /// ### Returns
+    /// `Option<BoundMethod>` - the method, or `None` if the type of the value has no such method
+    pub fn bind(&self, receiver: &Literal, name: &str) -> Option<BoundMethod> {
+        let method_type = MethodType::of(receiver)?;
+        let method = self.methods.get(&(method_type, name.to_string()))?;
+        return Some(BoundMethod {
+            receiver: receiver.clone(),
+            method: method.clone(),
+            name: format!("{}.{}", method_type.name(), name),
+        });
+    }
+
+    /// Names of the methods of a type, sorted
+    pub fn names(&self, method_type: MethodType) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .methods
+            .keys()
+            .filter(|(key_type, _)| *key_type == method_type)
+            .map(|(_, name)| name.to_string())
+            .collect();
+        names.sort();
+        return names;
+    }
+}
+
+/// A method together with the value it was looked up on, e.g. `"abc".upper`
+#[derive(Debug, Clone)]
+pub struct BoundMethod {
+    receiver: Literal,
+    method: Box<dyn QalamCallable>,
+    /// Type and name of the method, e.g. `string.upper`
+    name: String,
+}
+
+impl QalamCallable for BoundMethod {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let mut with_receiver = Vec::with_capacity(arguments.len() + 1);
+        with_receiver.push(Some(self.receiver.clone()));
+        with_receiver.extend(arguments);
+        return self
+            .method
+            .call(interpreter, with_receiver, paren)
+            .map_err(|mut error| {
+                // Arguments are numbered as they are written, without the receiver. Errors
+                // from calls further in (e.g. in a callback) have their own call site
+                if error.token == *paren {
+                    if let Some(mut argument) = error.argument.take() {
+                        argument.position = match argument.position {
+                            Some(position) if position > 1 => Some(position - 1),
+                            _ => None,
+                        };
+                        error.message = argument.message();
+                        error.argument = Some(argument);
+                    }
+                }
+                return error;
+            });
+    }
+
+    fn arity(&self) -> usize {
+        // The receiver is the first argument
+        return self.method.arity().saturating_sub(1);
+    }
+
+    fn max_arity(&self) -> Option<usize> {
+        return self
+            .method
+            .max_arity()
+            .map(|max_arity| max_arity.saturating_sub(1));
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return format!("<method {}>", self.name);
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_identical(&self, other: &dyn QalamCallable) -> bool {
+        return match other.as_any().downcast_ref::<Self>() {
+            Some(other) => {
+                other.receiver == self.receiver && other.method.is_identical(self.method.as_ref())
+            }
+            None => false,
+        };
+    }
+}
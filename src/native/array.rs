@@ -0,0 +1,352 @@
+use crate::hashable::HashableRcRefCell;
+use crate::literal::QalamArray;
+use crate::native::convert::{NativeArgs, MAX_INTEGER_ARGUMENT};
+use crate::native::*;
+use std::cmp::Ordering;
+
+/// Creates an array, accounting for the memory of its elements
+pub fn new_array(
+    elements: Vec<Option<Literal>>,
+    interpreter: &mut Interpreter,
+    paren: &Token,
+) -> Result<Option<Literal>, RuntimeError> {
+    interpreter.allocate_elements(elements.len(), paren)?;
+    return Ok(Some(Literal::Array(HashableRcRefCell::init(
+        QalamArray::from_vec(elements),
+    ))));
+}
+
+#[derive(Debug, Clone)]
+pub struct InsertFn {}
+
+impl InsertFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for InsertFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let args = NativeArgs::init(&arguments, &["arr", "index", "val"], paren);
+        let arr: HashableRcRefCell<QalamArray> = args.get(0)?;
+        let index: usize = args.get(1)?;
+        // Inserting at the length adds to the end
+        if index > arr.0.borrow().elements.len() {
+            return Err(RuntimeError::init(
+                paren,
+                String::from("index is out of range!"),
+            ));
+        }
+        interpreter.allocate_elements(1, paren)?;
+        arr.0
+            .borrow_mut()
+            .elements
+            .insert(index, arguments[2].clone());
+        return Ok(None);
+    }
+
+    fn arity(&self) -> usize {
+        return 3;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal insert(arr, index, val)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("insert")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoveAtFn {}
+
+impl RemoveAtFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for RemoveAtFn {
+    fn call(
+        &mut self,
+        _interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let args = NativeArgs::init(&arguments, &["arr", "index"], paren);
+        let arr: HashableRcRefCell<QalamArray> = args.get(0)?;
+        let index: usize = args.get(1)?;
+        if index >= arr.0.borrow().elements.len() {
+            return Err(RuntimeError::init(
+                paren,
+                String::from("index is out of range!"),
+            ));
+        }
+        return Ok(arr.0.borrow_mut().elements.remove(index));
+    }
+
+    fn arity(&self) -> usize {
+        return 2;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal remove_at(arr, index)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("remove_at")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConcatFn {}
+
+impl ConcatFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for ConcatFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let args = NativeArgs::init(&arguments, &["a", "b"], paren);
+        let a: HashableRcRefCell<QalamArray> = args.get(0)?;
+        let b: HashableRcRefCell<QalamArray> = args.get(1)?;
+        let mut elements = a.0.borrow().elements.clone();
+        elements.extend(b.0.borrow().elements.iter().cloned());
+        return new_array(elements, interpreter, paren);
+    }
+
+    fn arity(&self) -> usize {
+        return 2;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal concat(a, b)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("concat")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SumFn {}
+
+impl SumFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for SumFn {
+    fn call(
+        &mut self,
+        _interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let arr: HashableRcRefCell<QalamArray> =
+            NativeArgs::init(&arguments, &["arr"], paren).get(0)?;
+        let mut sum = 0.0;
+        for element in arr.0.borrow().elements.iter() {
+            match element {
+                Some(Literal::Number(num)) => sum += **num,
+                _ => {
+                    return Err(RuntimeError::init(
+                        paren,
+                        String::from("'arr' must contain only numbers!"),
+                    ))
+                }
+            }
+        }
+        return Ok(Some(Literal::Number(OrderedFloat(sum))));
+    }
+
+    fn arity(&self) -> usize {
+        return 1;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal sum(arr)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("sum")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RangeFn {}
+
+impl RangeFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for RangeFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let args = NativeArgs::init(&arguments, &["start", "end", "step?"], paren);
+        let start: f64 = args.get(0)?;
+        let end: f64 = args.get(1)?;
+        // The step is 1 when it isn't given
+        let step: f64 = args.get::<Option<f64>>(2)?.unwrap_or(1.0);
+        if !start.is_finite() {
+            return Err(args.invalid(0, "a finite number"));
+        }
+        if !end.is_finite() {
+            return Err(args.invalid(1, "a finite number"));
+        }
+        if step == 0.0 || !step.is_finite() {
+            return Err(args.invalid(2, "a finite number other than 0"));
+        }
+
+        // Allocated before the numbers are made, so a huge range is an error instead of running out of memory
+        let count = ((end - start) / step).ceil().max(0.0);
+        if count > MAX_INTEGER_ARGUMENT {
+            return Err(RuntimeError::init(
+                paren,
+                String::from("A range can't have more than 2^53 numbers!"),
+            ));
+        }
+        let count = count as usize;
+        let mut elements = interpreter.elements_with_capacity(count, paren)?;
+        elements.extend((0..count).map(|i| Some(Literal::Number(OrderedFloat(start + i as f64 * step)))));
+        return Ok(Some(Literal::Array(HashableRcRefCell::init(
+            QalamArray::from_vec(elements),
+        ))));
+    }
+
+    fn arity(&self) -> usize {
+        return 2;
+    }
+
+    fn max_arity(&self) -> Option<usize> {
+        return Some(3);
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal range(start, end, step = 1)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("range")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SortFn {}
+
+impl SortFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for SortFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let arr: HashableRcRefCell<QalamArray> =
+            NativeArgs::init(&arguments, &["arr"], paren).get(0)?;
+        let mut elements = arr.0.borrow().elements.clone();
+        let numbers = elements
+            .iter()
+            .all(|element| matches!(element, Some(Literal::Number(_))));
+        let strings = elements
+            .iter()
+            .all(|element| matches!(element, Some(Literal::String(_))));
+        if !numbers && !strings {
+            return Err(RuntimeError::init(
+                paren,
+                String::from("'arr' must contain only numbers or only strings!"),
+            ));
+        }
+        elements.sort_by(|a, b| match (a, b) {
+            (Some(Literal::Number(a)), Some(Literal::Number(b))) => a.cmp(b),
+            (Some(Literal::String(a)), Some(Literal::String(b))) => a.cmp(b),
+            _ => Ordering::Equal,
+        });
+        return new_array(elements, interpreter, paren);
+    }
+
+    fn arity(&self) -> usize {
+        return 1;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal sort(arr)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("sort")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -1,5 +1,6 @@
 use crate::hashable::HashableRcRefCell;
 use crate::literal::QalamArray;
+use crate::native::convert::NativeArgs;
 use crate::native::*;
 
 #[derive(Clone, Debug)]
@@ -14,27 +15,18 @@ impl ArrayConstructorFn {
 impl QalamCallable for ArrayConstructorFn {
     fn call(
         &mut self,
-        _interpreter: &mut Interpreter,
+        interpreter: &mut Interpreter,
         arguments: Vec<Option<Literal>>,
         paren: &Token,
     ) -> Result<Option<Literal>, RuntimeError> {
-        let size = &arguments[0];
-        let value = &arguments[1];
-        if let Some(Literal::Number(size)) = size {
-            if !is_usize(**size) {
-                return Err(RuntimeError::init(
-                    paren,
-                    format!("'size' must be a positive integer!"),
-                ));
-            }
-            let arr = QalamArray::construct(**size as usize, value.clone());
-            return Ok(Some(Literal::Array(HashableRcRefCell::init(arr))));
-        } else {
-            return Err(RuntimeError::init(
-                paren,
-                format!("'size' must be a number!"),
-            ));
-        }
+        let args = NativeArgs::init(&arguments, &["size", "value"], paren);
+        let size: usize = args.get(0)?;
+        let value: Option<Literal> = args.get(1)?;
+        let mut elements = interpreter.elements_with_capacity(size, paren)?;
+        elements.resize(size, value);
+        return Ok(Some(Literal::Array(HashableRcRefCell::init(
+            QalamArray::from_vec(elements),
+        ))));
     }
 
     fn arity(&self) -> usize {
@@ -45,6 +37,10 @@ impl QalamCallable for ArrayConstructorFn {
         return String::from("<native amal Array(size, value)>");
     }
 
+    fn name(&self) -> String {
+        String::from("Array")
+    }
+
     fn clone_box(&self) -> Box<dyn QalamCallable> {
         return Box::new(self.clone());
     }
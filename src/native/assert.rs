@@ -0,0 +1,183 @@
+use crate::native::*;
+
+/// Describes a value in an assertion failure, quoting strings so `"1"` and `1` can be told apart
+fn describe(value: &Option<Literal>) -> String {
+    return match value {
+        Some(Literal::String(val)) => format!("\"{}\"", val),
+        _ => Literal::option_string(value.clone()),
+    };
+}
+
+#[derive(Debug, Clone)]
+pub struct AssertEqualFn {}
+
+impl AssertEqualFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for AssertEqualFn {
+    fn call(
+        &mut self,
+        _interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let actual = &arguments[0];
+        let expected = &arguments[1];
+        if !Literal::deep_equal(actual, expected) {
+            return Err(RuntimeError::init(
+                paren,
+                format!(
+                    "Assertion failed: expected {} but got {}.",
+                    describe(expected),
+                    describe(actual)
+                ),
+            ));
+        }
+        return Ok(None);
+    }
+
+    fn arity(&self) -> usize {
+        return 2;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal assert_equal(actual, expected)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("assert_equal")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AssertNotEqualFn {}
+
+impl AssertNotEqualFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for AssertNotEqualFn {
+    fn call(
+        &mut self,
+        _interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let actual = &arguments[0];
+        let unexpected = &arguments[1];
+        if Literal::deep_equal(actual, unexpected) {
+            return Err(RuntimeError::init(
+                paren,
+                format!(
+                    "Assertion failed: expected a value other than {}.",
+                    describe(unexpected)
+                ),
+            ));
+        }
+        return Ok(None);
+    }
+
+    fn arity(&self) -> usize {
+        return 2;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal assert_not_equal(actual, unexpected)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("assert_not_equal")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AssertThrowsFn {}
+
+impl AssertThrowsFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for AssertThrowsFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let callee = match &arguments[0] {
+            Some(Literal::Callable(callee)) => callee.clone(),
+            value => {
+                return Err(RuntimeError::init(
+                    paren,
+                    format!("Expected a kaam to call but got {}.", describe(value)),
+                ))
+            }
+        };
+        // Otherwise the arity error of the call would count as the error being tested for
+        if callee.arity() != 0 {
+            return Err(RuntimeError::init(
+                paren,
+                format!(
+                    "Assertion failed: expected a kaam that takes no arguments but {} takes {}.",
+                    callee.to_string(),
+                    callee.arity()
+                ),
+            ));
+        }
+        return match interpreter.call(callee, Vec::new(), paren) {
+            // Running out of steps or time isn't the error being tested for
+            Err(e) if e.limit.is_some() || e.exit_code.is_some() => Err(e),
+            Err(_) => Ok(None),
+            Ok(value) => Err(RuntimeError::init(
+                paren,
+                format!(
+                    "Assertion failed: expected an error but the kaam returned {}.",
+                    describe(&value)
+                ),
+            )),
+        };
+    }
+
+    fn arity(&self) -> usize {
+        return 1;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal assert_throws(f)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("assert_throws")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,43 @@
+use crate::native::*;
+
+#[derive(Debug, Clone)]
+pub struct BarabarFn {}
+
+impl BarabarFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for BarabarFn {
+    fn call(
+        &mut self,
+        _interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        _paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let a = &arguments[0];
+        let b = &arguments[1];
+        return Ok(Some(Literal::Bool(Literal::deep_equal(a, b))));
+    }
+
+    fn arity(&self) -> usize {
+        return 2;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal barabar(a, b)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("barabar")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -35,6 +35,10 @@ impl QalamCallable for CeilFn {
         return String::from("<native amal ceil(num)>");
     }
 
+    fn name(&self) -> String {
+        String::from("ceil")
+    }
+
     fn clone_box(&self) -> Box<dyn QalamCallable> {
         return Box::new(self.clone());
     }
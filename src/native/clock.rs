@@ -1,5 +1,8 @@
 use crate::native::*;
 
+/// Milliseconds the virtual clock advances by on each call in deterministic mode
+pub const VIRTUAL_CLOCK_TICK_MILLIS: u64 = 1;
+
 #[derive(Clone, Debug)]
 pub struct ClockFn {}
 
@@ -12,10 +15,14 @@ impl ClockFn {
 impl QalamCallable for ClockFn {
     fn call(
         &mut self,
-        _interpreter: &mut Interpreter,
+        interpreter: &mut Interpreter,
         _arguments: Vec<Option<Literal>>,
         _paren: &Token,
     ) -> Result<Option<Literal>, RuntimeError> {
+        if let Some(millis) = interpreter.virtual_clock {
+            interpreter.virtual_clock = Some(millis + VIRTUAL_CLOCK_TICK_MILLIS);
+            return Ok(Some(Literal::Number(OrderedFloat(millis as f64 / 1000.0))));
+        }
         let start = std::time::SystemTime::now();
         let since_epoch = start
             .duration_since(std::time::UNIX_EPOCH)
@@ -32,6 +39,10 @@ impl QalamCallable for ClockFn {
         return String::from("<native amal clock>");
     }
 
+    fn name(&self) -> String {
+        String::from("clock")
+    }
+
     fn clone_box(&self) -> Box<dyn QalamCallable> {
         return Box::new(self.clone());
     }
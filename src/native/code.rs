@@ -42,6 +42,10 @@ impl QalamCallable for CodeFn {
         return String::from("<native amal code(char)>");
     }
 
+    fn name(&self) -> String {
+        String::from("code")
+    }
+
     fn clone_box(&self) -> Box<dyn QalamCallable> {
         return Box::new(self.clone());
     }
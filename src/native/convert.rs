@@ -0,0 +1,336 @@
+use crate::error::ArgumentError;
+use crate::hashable::HashableRcRefCell;
+use crate::literal::QalamArray;
+use crate::native::*;
+
+/// Largest integer an argument can be (2^53), larger numbers can't be told apart from their
+/// neighbours and would saturate when converted to a Rust integer
+pub(crate) const MAX_INTEGER_ARGUMENT: f64 = 9007199254740992.0;
+
+/// Conversion of an argument to a Rust type, used by `NativeArgs::get`
+pub trait FromLiteral: Sized {
+    /// Converts the value, or returns `None` if it has the wrong type
+    fn from_literal(value: &Option<Literal>) -> Option<Self>;
+
+    /// What the value must be, used in the error message, e.g. "a number"
+    fn expected() -> String;
+}
+
+impl FromLiteral for f64 {
+    fn from_literal(value: &Option<Literal>) -> Option<Self> {
+        return match value {
+            Some(Literal::Number(num)) => Some(**num),
+            _ => None,
+        };
+    }
+
+    fn expected() -> String {
+        return String::from("a number");
+    }
+}
+
+impl FromLiteral for i64 {
+    fn from_literal(value: &Option<Literal>) -> Option<Self> {
+        return match value {
+            Some(Literal::Number(num)) if is_int(**num) && num.abs() <= MAX_INTEGER_ARGUMENT => {
+                Some(**num as i64)
+            }
+            _ => None,
+        };
+    }
+
+    fn expected() -> String {
+        return String::from("an integer between -2^53 and 2^53");
+    }
+}
+
+impl FromLiteral for usize {
+    fn from_literal(value: &Option<Literal>) -> Option<Self> {
+        return match value {
+            Some(Literal::Number(num)) if is_usize(**num) && **num <= MAX_INTEGER_ARGUMENT => {
+                Some(**num as usize)
+            }
+            _ => None,
+        };
+    }
+
+    fn expected() -> String {
+        return String::from("a positive integer no larger than 2^53");
+    }
+}
+
+impl FromLiteral for bool {
+    fn from_literal(value: &Option<Literal>) -> Option<Self> {
+        return match value {
+            Some(Literal::Bool(b)) => Some(*b),
+            _ => None,
+        };
+    }
+
+    fn expected() -> String {
+        return String::from("a boolean");
+    }
+}
+
+impl FromLiteral for String {
+    fn from_literal(value: &Option<Literal>) -> Option<Self> {
+        return match value {
+            Some(Literal::String(s)) => Some(s.to_string()),
+            _ => None,
+        };
+    }
+
+    fn expected() -> String {
+        return String::from("a string");
+    }
+}
+
+/// The array itself rather than a copy, so changes to it are seen by the program
+impl FromLiteral for HashableRcRefCell<QalamArray> {
+    fn from_literal(value: &Option<Literal>) -> Option<Self> {
+        return match value {
+            Some(Literal::Array(arr)) => Some(arr.clone()),
+            _ => None,
+        };
+    }
+
+    fn expected() -> String {
+        return String::from("an array");
+    }
+}
+
+impl FromLiteral for Box<dyn QalamCallable> {
+    fn from_literal(value: &Option<Literal>) -> Option<Self> {
+        return match value {
+            Some(Literal::Callable(callable)) => Some(callable.clone()),
+            _ => None,
+        };
+    }
+
+    fn expected() -> String {
+        return String::from("a function");
+    }
+}
+
+/// Any value except `khali`
+impl FromLiteral for Literal {
+    fn from_literal(value: &Option<Literal>) -> Option<Self> {
+        return value.clone();
+    }
+
+    fn expected() -> String {
+        return String::from("a value");
+    }
+}
+
+/// `khali` or a missing optional argument becomes `None`, so `Option<Literal>` takes any value
+impl<T: FromLiteral> FromLiteral for Option<T> {
+    fn from_literal(value: &Option<Literal>) -> Option<Self> {
+        return match value {
+            None => Some(None),
+            value => T::from_literal(value).map(Some),
+        };
+    }
+
+    fn expected() -> String {
+        return format!("{} or khali", T::expected());
+    }
+}
+
+/// Conversion of a Rust value returned by a native to a value of the program
+/// Strings and arrays are accounted for in the interpreter's memory limit
+pub trait IntoLiteral {
+    fn into_literal(
+        self,
+        interpreter: &mut Interpreter,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError>;
+}
+
+impl IntoLiteral for f64 {
+    fn into_literal(self, _: &mut Interpreter, _: &Token) -> Result<Option<Literal>, RuntimeError> {
+        return Ok(Some(Literal::Number(OrderedFloat(self))));
+    }
+}
+
+impl IntoLiteral for i64 {
+    fn into_literal(self, _: &mut Interpreter, _: &Token) -> Result<Option<Literal>, RuntimeError> {
+        return Ok(Some(Literal::Number(OrderedFloat(self as f64))));
+    }
+}
+
+impl IntoLiteral for usize {
+    fn into_literal(self, _: &mut Interpreter, _: &Token) -> Result<Option<Literal>, RuntimeError> {
+        return Ok(Some(Literal::Number(OrderedFloat(self as f64))));
+    }
+}
+
+impl IntoLiteral for bool {
+    fn into_literal(self, _: &mut Interpreter, _: &Token) -> Result<Option<Literal>, RuntimeError> {
+        return Ok(Some(Literal::Bool(self)));
+    }
+}
+
+impl IntoLiteral for String {
+    fn into_literal(
+        self,
+        interpreter: &mut Interpreter,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        interpreter.allocate(self.len(), paren)?;
+        return Ok(Some(Literal::String(self)));
+    }
+}
+
+/// Returns `khali`
+impl IntoLiteral for () {
+    fn into_literal(self, _: &mut Interpreter, _: &Token) -> Result<Option<Literal>, RuntimeError> {
+        return Ok(None);
+    }
+}
+
+impl IntoLiteral for HashableRcRefCell<QalamArray> {
+    fn into_literal(self, _: &mut Interpreter, _: &Token) -> Result<Option<Literal>, RuntimeError> {
+        return Ok(Some(Literal::Array(self)));
+    }
+}
+
+impl IntoLiteral for Box<dyn QalamCallable> {
+    fn into_literal(self, _: &mut Interpreter, _: &Token) -> Result<Option<Literal>, RuntimeError> {
+        return Ok(Some(Literal::Callable(self)));
+    }
+}
+
+impl IntoLiteral for Literal {
+    fn into_literal(self, _: &mut Interpreter, _: &Token) -> Result<Option<Literal>, RuntimeError> {
+        return Ok(Some(self));
+    }
+}
+
+/// `None` becomes `khali`
+impl<T: IntoLiteral> IntoLiteral for Option<T> {
+    fn into_literal(
+        self,
+        interpreter: &mut Interpreter,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        return match self {
+            Some(value) => value.into_literal(interpreter, paren),
+            None => Ok(None),
+        };
+    }
+}
+
+/// Creates a new array
+impl<T: IntoLiteral> IntoLiteral for Vec<T> {
+    fn into_literal(
+        self,
+        interpreter: &mut Interpreter,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        interpreter.allocate_elements(self.len(), paren)?;
+        let mut elements = Vec::with_capacity(self.len());
+        for value in self.into_iter() {
+            elements.push(value.into_literal(interpreter, paren)?);
+        }
+        return Ok(Some(Literal::Array(HashableRcRefCell::init(
+            QalamArray::from_vec(elements),
+        ))));
+    }
+}
+
+/// An error message becomes a `RuntimeError` at the call
+impl<T: IntoLiteral> IntoLiteral for Result<T, String> {
+    fn into_literal(
+        self,
+        interpreter: &mut Interpreter,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        return match self {
+            Ok(value) => value.into_literal(interpreter, paren),
+            Err(message) => Err(RuntimeError::init(paren, message)),
+        };
+    }
+}
+
+impl<T: IntoLiteral> IntoLiteral for Result<T, RuntimeError> {
+    fn into_literal(
+        self,
+        interpreter: &mut Interpreter,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        return self?.into_literal(interpreter, paren);
+    }
+}
+
+/// The arguments of a call to a native, with the names of its parameters
+/// so errors about an argument say which one it is
+pub struct NativeArgs<'a> {
+    arguments: &'a [Option<Literal>],
+    params: &'a [&'a str],
+    pub paren: &'a Token,
+}
+
+impl<'a> NativeArgs<'a> {
+    /// `params` are the parameter names, with a `...` before the last one if it collects the rest
+    /// and a `?` after the optional ones
+    pub fn init(arguments: &'a [Option<Literal>], params: &'a [&'a str], paren: &'a Token) -> Self {
+        return Self {
+            arguments,
+            params,
+            paren,
+        };
+    }
+
+    /// Number of arguments passed
+    pub fn len(&self) -> usize {
+        return self.arguments.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.arguments.is_empty();
+    }
+
+    /// Converts the argument at `index`
+    /// A missing (optional) argument is `khali`, so it converts to `Option<T>` as `None`
+    ///
+    /// ### Returns
+    /// `Result<T, RuntimeError>` - the value, or an error naming the parameter if it has the wrong type
+    pub fn get<T: FromLiteral>(&self, index: usize) -> Result<T, RuntimeError> {
+        let value = self.arguments.get(index).cloned().unwrap_or(None);
+        return T::from_literal(&value).ok_or_else(|| self.invalid(index, &T::expected()));
+    }
+
+    /// Error for an argument that has the right type but not an allowed value,
+    /// worded like the errors of `get`, e.g. `invalid(2, "a number other than 0")`
+    pub fn invalid(&self, index: usize, expected: &str) -> RuntimeError {
+        let value = self.arguments.get(index).cloned().unwrap_or(None);
+        return RuntimeError::init_argument(
+            self.paren,
+            ArgumentError {
+                position: Some(index + 1),
+                param: self.param(index).to_string(),
+                value: Literal::option_string(value),
+                expected: expected.to_string(),
+            },
+        );
+    }
+
+    /// Converts each of the arguments from `index` on, e.g. the ones collected by a rest parameter
+    pub fn rest<T: FromLiteral>(&self, index: usize) -> Result<Vec<T>, RuntimeError> {
+        let mut values = Vec::new();
+        for index in index..self.arguments.len() {
+            values.push(self.get(index)?);
+        }
+        return Ok(values);
+    }
+
+    /// Name of the parameter an argument is for, extra arguments belong to the rest parameter
+    fn param(&self, index: usize) -> &str {
+        let param = match self.params.get(index).or(self.params.last()) {
+            Some(param) => param,
+            None => return "arg",
+        };
+        return param.trim_start_matches("...").trim_end_matches('?');
+    }
+}
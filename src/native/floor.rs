@@ -35,6 +35,10 @@ impl QalamCallable for FloorFn {
         return String::from("<native amal floor(num)>");
     }
 
+    fn name(&self) -> String {
+        String::from("floor")
+    }
+
     fn clone_box(&self) -> Box<dyn QalamCallable> {
         return Box::new(self.clone());
     }
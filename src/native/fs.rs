@@ -0,0 +1,298 @@
+use crate::hashable::HashableRcRefCell;
+use crate::literal::QalamArray;
+use crate::native::convert::NativeArgs;
+use crate::native::*;
+use crate::permissions::Access;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Gets a path argument and checks the program is allowed to access it
+fn checked_path(
+    interpreter: &Interpreter,
+    args: &NativeArgs,
+    access: Access,
+) -> Result<PathBuf, RuntimeError> {
+    let path: String = args.get(0)?;
+    return interpreter
+        .permissions
+        .check(access, &path)
+        .map_err(|message| RuntimeError::init(args.paren, message));
+}
+
+/// Opens a file for writing without following a symbolic link in its place, so a link
+/// created after the path was checked can't redirect the write outside the allowed paths
+fn open_for_write(path: &Path) -> std::io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_NOFOLLOW);
+    }
+    return options.open(path);
+}
+
+fn io_error(paren: &Token, action: &str, path: &Path, e: std::io::Error) -> RuntimeError {
+    return RuntimeError::init(
+        paren,
+        format!("Cannot {} '{}': {}.", action, path.display(), e),
+    );
+}
+
+#[derive(Debug, Clone)]
+pub struct ReadFileFn {}
+
+impl ReadFileFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for ReadFileFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let path = checked_path(
+            interpreter,
+            &NativeArgs::init(&arguments, &["path"], paren),
+            Access::Read,
+        )?;
+        let text = std::fs::read_to_string(&path).map_err(|e| io_error(paren, "read", &path, e))?;
+        interpreter.allocate(text.len(), paren)?;
+        return Ok(Some(Literal::String(text)));
+    }
+
+    fn arity(&self) -> usize {
+        return 1;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal read_file(path)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("read_file")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReadLinesFn {}
+
+impl ReadLinesFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for ReadLinesFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let path = checked_path(
+            interpreter,
+            &NativeArgs::init(&arguments, &["path"], paren),
+            Access::Read,
+        )?;
+        let text = std::fs::read_to_string(&path).map_err(|e| io_error(paren, "read", &path, e))?;
+        let lines: Vec<Option<Literal>> = text
+            .lines()
+            .map(|line| Some(Literal::String(line.to_string())))
+            .collect();
+        interpreter.allocate(text.len(), paren)?;
+        interpreter.allocate_elements(lines.len(), paren)?;
+        return Ok(Some(Literal::Array(HashableRcRefCell::init(
+            QalamArray::from_vec(lines),
+        ))));
+    }
+
+    fn arity(&self) -> usize {
+        return 1;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal read_lines(path)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("read_lines")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WriteFileFn {}
+
+impl WriteFileFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for WriteFileFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let path = checked_path(
+            interpreter,
+            &NativeArgs::init(&arguments, &["path", "text"], paren),
+            Access::Write,
+        )?;
+        let text = match &arguments[1] {
+            Some(Literal::String(text)) => text.to_string(),
+            value => Literal::option_string(value.clone()),
+        };
+        open_for_write(&path)
+            .and_then(|mut file| file.write_all(text.as_bytes()))
+            .map_err(|e| io_error(paren, "write", &path, e))?;
+        return Ok(None);
+    }
+
+    fn arity(&self) -> usize {
+        return 2;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal write_file(path, text)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("write_file")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListDirFn {}
+
+impl ListDirFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for ListDirFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let path = checked_path(
+            interpreter,
+            &NativeArgs::init(&arguments, &["path"], paren),
+            Access::Read,
+        )?;
+        let entries = std::fs::read_dir(&path).map_err(|e| io_error(paren, "list", &path, e))?;
+        let mut names = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| io_error(paren, "list", &path, e))?;
+            names.push(entry.file_name().to_string_lossy().to_string());
+        }
+        names.sort();
+        interpreter.allocate_elements(names.len(), paren)?;
+        let names = names
+            .into_iter()
+            .map(|name| Some(Literal::String(name)))
+            .collect();
+        return Ok(Some(Literal::Array(HashableRcRefCell::init(
+            QalamArray::from_vec(names),
+        ))));
+    }
+
+    fn arity(&self) -> usize {
+        return 1;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal list_dir(path)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("list_dir")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PathExistsFn {}
+
+impl PathExistsFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for PathExistsFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let path = checked_path(
+            interpreter,
+            &NativeArgs::init(&arguments, &["path"], paren),
+            Access::Read,
+        )?;
+        return Ok(Some(Literal::Bool(path.exists())));
+    }
+
+    fn arity(&self) -> usize {
+        return 1;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal path_exists(path)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("path_exists")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
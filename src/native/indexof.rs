@@ -16,6 +16,16 @@ impl QalamCallable for IndexOfFn {
         arguments: Vec<Option<Literal>>,
         paren: &Token,
     ) -> Result<Option<Literal>, RuntimeError> {
+        if let Some(Literal::Array(arr)) = &arguments[0] {
+            let position = arr
+                .0
+                .borrow()
+                .elements
+                .iter()
+                .position(|element| *element == arguments[1]);
+            let index = position.map(|index| index as f64).unwrap_or(-1.0);
+            return Ok(Some(Literal::Number(OrderedFloat(index))));
+        }
         let arg = &arguments[0];
         let substring = &arguments[1];
         if let (Some(arg), Some(substring)) = (arg, substring) {
@@ -28,7 +38,7 @@ impl QalamCallable for IndexOfFn {
             } else {
                 return Err(RuntimeError::init(
                     paren,
-                    format!("'arg' and 'substring' must be strings!"),
+                    String::from("'arg' must be an array, or 'arg' and 'substring' must be strings!"),
                 ));
             }
         } else {
@@ -47,6 +57,10 @@ impl QalamCallable for IndexOfFn {
         return String::from("<native amal index_of(arg, substring)>");
     }
 
+    fn name(&self) -> String {
+        String::from("index_of")
+    }
+
     fn clone_box(&self) -> Box<dyn QalamCallable> {
         return Box::new(self.clone());
     }
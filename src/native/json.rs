@@ -0,0 +1,238 @@
+use crate::callable::class::QalamClass;
+use crate::callable::instance::QalamInstance;
+use crate::hashable::{HashableMap, HashableRcRefCell};
+use crate::literal::QalamArray;
+use crate::native::convert::NativeArgs;
+use crate::native::*;
+use serde_json::{Map, Number, Value};
+
+/// Name of the jamat JSON objects become instances of
+pub const JSON_OBJECT_CLASS: &str = "Object";
+
+/// Converts parsed JSON into qalam values
+/// Objects become instances of an `Object` jamat with a field for each key
+fn from_json(
+    value: Value,
+    class: &QalamClass,
+    interpreter: &mut Interpreter,
+    paren: &Token,
+) -> Result<Option<Literal>, RuntimeError> {
+    return match value {
+        Value::Null => Ok(None),
+        Value::Bool(val) => Ok(Some(Literal::Bool(val))),
+        Value::Number(val) => Ok(Some(Literal::Number(OrderedFloat(
+            val.as_f64().unwrap_or(f64::NAN),
+        )))),
+        Value::String(val) => {
+            interpreter.allocate(val.len(), paren)?;
+            Ok(Some(Literal::String(val)))
+        }
+        Value::Array(values) => {
+            interpreter.allocate_elements(values.len(), paren)?;
+            let mut elements = Vec::new();
+            for value in values.into_iter() {
+                elements.push(from_json(value, class, interpreter, paren)?);
+            }
+            Ok(Some(Literal::Array(HashableRcRefCell::init(
+                QalamArray::from_vec(elements),
+            ))))
+        }
+        Value::Object(fields) => {
+            interpreter.allocate_elements(fields.len(), paren)?;
+            let mut instance = QalamInstance::init(class.clone());
+            for (key, value) in fields.into_iter() {
+                let value = from_json(value, class, interpreter, paren)?;
+                instance.fields.insert(key, value);
+            }
+            Ok(Some(Literal::Instance(HashableRcRefCell::init(instance))))
+        }
+    };
+}
+
+/// Converts a qalam value to JSON
+///
+/// ### Arguments
+/// `path` - addresses of the arrays and instances being converted, for finding cycles
+fn to_json(value: &Option<Literal>, path: &mut Vec<usize>, paren: &Token) -> Result<Value, RuntimeError> {
+    let cyclic = || {
+        return RuntimeError::init(
+            paren,
+            String::from("Cannot convert a cyclic structure to JSON."),
+        );
+    };
+    return match value {
+        None => Ok(Value::Null),
+        Some(Literal::Bool(val)) => Ok(Value::Bool(*val)),
+        Some(Literal::Number(val)) => {
+            let val = **val;
+            // Whole numbers are written without a fraction, e.g. `1` instead of `1.0`
+            if is_int(val) && val.abs() < 9007199254740992.0 {
+                return Ok(Value::Number(Number::from(val as i64)));
+            }
+            match Number::from_f64(val) {
+                Some(number) => Ok(Value::Number(number)),
+                None => Err(RuntimeError::init(
+                    paren,
+                    format!("Cannot convert {} to JSON.", val),
+                )),
+            }
+        }
+        Some(Literal::String(val)) => Ok(Value::String(val.to_string())),
+        Some(Literal::Array(array)) => {
+            let address = std::rc::Rc::as_ptr(&array.0) as usize;
+            if path.contains(&address) {
+                return Err(cyclic());
+            }
+            path.push(address);
+            let mut values = Vec::new();
+            for element in array.0.borrow().elements.iter() {
+                values.push(to_json(element, path, paren)?);
+            }
+            path.pop();
+            Ok(Value::Array(values))
+        }
+        Some(Literal::Instance(instance)) => {
+            let address = std::rc::Rc::as_ptr(&instance.0) as usize;
+            if path.contains(&address) {
+                return Err(cyclic());
+            }
+            path.push(address);
+            let instance = instance.0.borrow();
+            // Sorted so the output doesn't depend on the order of the fields in memory
+            let mut entries: Vec<(&String, &Option<Literal>)> = instance.fields.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let mut fields = Map::new();
+            for (name, value) in entries {
+                fields.insert(name.to_string(), to_json(value, path, paren)?);
+            }
+            path.pop();
+            Ok(Value::Object(fields))
+        }
+        Some(Literal::Callable(callable)) => Err(RuntimeError::init(
+            paren,
+            format!("Cannot convert {} to JSON.", callable.to_string()),
+        )),
+    };
+}
+
+#[derive(Debug, Clone)]
+pub struct JsonParseFn {}
+
+impl JsonParseFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for JsonParseFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let text: String = NativeArgs::init(&arguments, &["text"], paren).get(0)?;
+        let value: Value = match serde_json::from_str(&text) {
+            Ok(value) => value,
+            Err(e) => {
+                // serde_json appends the position to its messages, which is reported separately
+                let message = e.to_string();
+                let message = message.split(" at line ").next().unwrap_or("");
+                return Err(RuntimeError::init(
+                    paren,
+                    format!(
+                        "Invalid JSON at line {}, column {}: {}.",
+                        e.line(),
+                        e.column(),
+                        message
+                    ),
+                ));
+            }
+        };
+        let class = QalamClass::init(String::from(JSON_OBJECT_CLASS), HashableMap::new(), None);
+        return from_json(value, &class, interpreter, paren);
+    }
+
+    fn arity(&self) -> usize {
+        return 1;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal json_parse(text)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("json_parse")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JsonStringifyFn {
+    /// Indent nested values on their own lines
+    pretty: bool,
+}
+
+impl JsonStringifyFn {
+    pub fn init(pretty: bool) -> Self {
+        return Self { pretty };
+    }
+}
+
+impl QalamCallable for JsonStringifyFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let value = to_json(&arguments[0], &mut Vec::new(), paren)?;
+        let text = match self.pretty {
+            true => serde_json::to_string_pretty(&value),
+            false => serde_json::to_string(&value),
+        };
+        let text = text.unwrap_or_default();
+        interpreter.allocate(text.len(), paren)?;
+        return Ok(Some(Literal::String(text)));
+    }
+
+    fn arity(&self) -> usize {
+        return 1;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return match self.pretty {
+            true => String::from("<native amal json_pretty(value)>"),
+            false => String::from("<native amal json_stringify(value)>"),
+        };
+    }
+
+    fn name(&self) -> String {
+        match self.pretty {
+            true => String::from("json_pretty"),
+            false => String::from("json_stringify"),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_identical(&self, other: &dyn QalamCallable) -> bool {
+        return match other.as_any().downcast_ref::<Self>() {
+            Some(other) => other.pretty == self.pretty,
+            None => false,
+        };
+    }
+}
@@ -56,6 +56,10 @@ impl QalamCallable for LenFn {
         return String::from("<native amal len(arg)>");
     }
 
+    fn name(&self) -> String {
+        String::from("len")
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -0,0 +1,139 @@
+use crate::callable::class::QalamClass;
+use crate::callable::instance::QalamInstance;
+use crate::hashable::{HashableMap, HashableRcRefCell};
+use crate::native::ceil::CeilFn;
+use crate::native::convert::NativeArgs;
+use crate::native::floor::FloorFn;
+use crate::native::max::MaxFn;
+use crate::native::min::MinFn;
+use crate::native::native_fn::NativeFn;
+use crate::native::pow::PowFn;
+use crate::native::round::RoundFn;
+use crate::native::*;
+
+/// Name of the global holding the math functions and constants
+pub const MATH_GLOBAL: &str = "math";
+
+/// Creates the `math` namespace, an instance with a field for each function and constant
+/// so they are used as `math.sqrt(2)` instead of filling up the globals
+pub fn math_namespace() -> Option<Literal> {
+    let class = QalamClass::init(String::from(MATH_GLOBAL), HashableMap::new(), None);
+    let mut namespace = QalamInstance::init_namespace(class);
+
+    let natives = [
+        NativeFn::from_fn("math.sqrt", &["x"], f64::sqrt),
+        NativeFn::from_fn("math.abs", &["x"], f64::abs),
+        NativeFn::from_fn("math.sin", &["x"], f64::sin),
+        NativeFn::from_fn("math.cos", &["x"], f64::cos),
+        NativeFn::from_fn("math.tan", &["x"], f64::tan),
+        NativeFn::from_fn("math.exp", &["x"], f64::exp),
+        NativeFn::from_fn("math.log", &["x"], f64::ln),
+        NativeFn::from_fn("math.log10", &["x"], f64::log10),
+        NativeFn::from_fn("math.log2", &["x"], f64::log2),
+        NativeFn::from_fn("math.sign", &["x"], sign),
+        NativeFn::from_fn("math.trunc", &["x"], f64::trunc),
+        NativeFn::from_fn("math.atan2", &["y", "x"], f64::atan2),
+        NativeFn::from_fn("math.hypot", &["x", "y"], f64::hypot),
+        NativeFn::from_fn("math.clamp", &["x", "min", "max"], clamp),
+        NativeFn::from_fn("math.gcd", &["a", "b"], gcd),
+        NativeFn::from_fn("math.lcm", &["a", "b"], lcm),
+        NativeFn::init("math.is_prime", &["n"], is_prime),
+    ];
+    for native in natives.into_iter() {
+        let name = native.name().trim_start_matches("math.").to_string();
+        namespace
+            .fields
+            .insert(name, Some(Literal::Callable(Box::new(native))));
+    }
+
+    // Also globals, repeated so all of math can be found in one place
+    let functions: Vec<(&str, Box<dyn QalamCallable>)> = vec![
+        ("pow", Box::new(PowFn::init())),
+        ("floor", Box::new(FloorFn::init())),
+        ("ceil", Box::new(CeilFn::init())),
+        ("round", Box::new(RoundFn::init())),
+        ("max", Box::new(MaxFn::init())),
+        ("min", Box::new(MinFn::init())),
+    ];
+    for (name, function) in functions.into_iter() {
+        namespace
+            .fields
+            .insert(name.to_string(), Some(Literal::Callable(function)));
+    }
+
+    let constants = [
+        ("PI", std::f64::consts::PI),
+        ("E", std::f64::consts::E),
+        ("TAU", std::f64::consts::TAU),
+        ("INFINITY", f64::INFINITY),
+    ];
+    for (name, value) in constants.into_iter() {
+        namespace
+            .fields
+            .insert(name.to_string(), Some(Literal::Number(OrderedFloat(value))));
+    }
+    return Some(Literal::Instance(HashableRcRefCell::init(namespace)));
+}
+
+fn sign(x: f64) -> f64 {
+    // f64::signum gives 1 for 0
+    if x == 0.0 || x.is_nan() {
+        return x;
+    }
+    return x.signum();
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    return a;
+}
+
+fn lcm(a: i64, b: i64) -> Result<i64, String> {
+    if a == 0 || b == 0 {
+        return Ok(0);
+    }
+    return (a / gcd(a, b))
+        .checked_mul(b)
+        // Larger results would lose precision as numbers
+        .filter(|res| res.abs() <= MAX_SAFE_INTEGER as i64)
+        .map(i64::abs)
+        .ok_or_else(|| String::from("'a' and 'b' have an lcm too large to represent!"));
+}
+
+fn clamp(x: f64, min: f64, max: f64) -> Result<f64, String> {
+    if min > max {
+        return Err(format!(
+            "'min' = {} must not be greater than 'max' = {}!",
+            min, max
+        ));
+    }
+    return Ok(x.max(min).min(max));
+}
+
+/// Number of divisors `is_prime` tries between checks of the timeout
+const DIVISORS_PER_DEADLINE_CHECK: i64 = 1 << 16;
+
+fn is_prime(
+    interpreter: &mut Interpreter,
+    args: &NativeArgs,
+) -> Result<Option<Literal>, RuntimeError> {
+    let n: i64 = args.get(0)?;
+    if n < 2 {
+        return Ok(Some(Literal::Bool(false)));
+    }
+    let mut divisor = 2;
+    while divisor * divisor <= n {
+        if n % divisor == 0 {
+            return Ok(Some(Literal::Bool(false)));
+        }
+        // Large primes take up to 10^8 divisions, which mustn't outlast `--timeout`
+        if divisor % DIVISORS_PER_DEADLINE_CHECK == 0 {
+            interpreter.check_deadline(args.paren)?;
+        }
+        divisor += 1;
+    }
+    return Ok(Some(Literal::Bool(true)));
+}
@@ -16,37 +16,39 @@ impl QalamCallable for MinFn {
         arguments: Vec<Option<Literal>>,
         paren: &Token,
     ) -> Result<Option<Literal>, RuntimeError> {
-        let a = &arguments[0];
-        let b = &arguments[1];
-        if let (Some(a), Some(b)) = (a, b) {
-            if let (Literal::Number(a), Literal::Number(b)) = (a, b) {
-                let res;
-                if a < b {
-                    res = *a;
-                } else {
-                    res = *b;
+        let mut res: Option<OrderedFloat<f64>> = None;
+        for argument in arguments.iter() {
+            match argument {
+                Some(Literal::Number(num)) => {
+                    if res.is_none() || res.is_some_and(|res| *num < res) {
+                        res = Some(*num);
+                    }
+                }
+                _ => {
+                    return Err(RuntimeError::init(
+                        paren,
+                        format!("{} must be called with number types!", self.to_string()),
+                    ))
                 }
-                return Ok(Some(Literal::Number(res)));
-            } else {
-                return Err(RuntimeError::init(
-                    paren,
-                    format!("{} must be called with number types!", self.to_string()),
-                ));
             }
-        } else {
-            return Err(RuntimeError::init(
-                paren,
-                format!("{} must be called with number types!", self.to_string()),
-            ));
         }
+        return Ok(res.map(Literal::Number));
     }
 
     fn arity(&self) -> usize {
-        return 2;
+        return 1;
+    }
+
+    fn max_arity(&self) -> Option<usize> {
+        return None;
     }
 
     fn to_string(&self) -> String {
-        return String::from("<native amal min(a, b)>");
+        return String::from("<native amal min(...nums)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("min")
     }
 
     fn clone_box(&self) -> Box<dyn QalamCallable> {
@@ -5,28 +5,42 @@ use crate::literal::Literal;
 use crate::token::Token;
 use ordered_float::OrderedFloat;
 use rand::Rng;
+pub mod array;
 pub mod array_constructor;
+pub mod assert;
+pub mod barabar;
 pub mod ceil;
 pub mod clock;
 pub mod code;
+pub mod convert;
 pub mod floor;
+pub mod fs;
 pub mod indexof;
+pub mod json;
 pub mod len;
+pub mod math;
 pub mod max;
 pub mod min;
+pub mod native_fn;
 pub mod num;
 pub mod pop;
 pub mod pow;
+pub mod process;
 pub mod push;
 pub mod random;
 pub mod random_int;
 pub mod replace;
 pub mod round;
+pub mod seed;
 pub mod slice;
 pub mod str;
+pub mod string;
 pub mod substr;
 pub mod typeof_func;
 
+/// Largest integer a number can hold exactly
+pub const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
 pub fn is_neg(num: f64) -> bool {
     return num < 0.0;
 }
@@ -0,0 +1,198 @@
+use crate::native::convert::{FromLiteral, IntoLiteral, NativeArgs};
+use crate::native::*;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// Body of a `NativeFn`
+pub trait NativeBody {
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        args: &NativeArgs,
+    ) -> Result<Option<Literal>, RuntimeError>;
+}
+
+impl<F> NativeBody for F
+where
+    F: Fn(&mut Interpreter, &NativeArgs) -> Result<Option<Literal>, RuntimeError>,
+{
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        args: &NativeArgs,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        return self(interpreter, args);
+    }
+}
+
+/// A native made from a closure, so it doesn't need its own type implementing `QalamCallable`
+///
+/// ```ignore
+/// Interpreter::add_global(globals, "hypot", NativeFn::from_fn("hypot", &["x", "y"], f64::hypot));
+/// ```
+#[derive(Clone)]
+pub struct NativeFn {
+    name: &'static str,
+    /// Names of the parameters, with a `?` after optional ones and `...` before a rest parameter
+    params: &'static [&'static str],
+    body: Rc<dyn NativeBody>,
+}
+
+impl NativeFn {
+    /// Creates a native whose body gets the interpreter and the arguments, and converts them itself
+    pub fn init<F>(name: &'static str, params: &'static [&'static str], body: F) -> Self
+    where
+        F: Fn(&mut Interpreter, &NativeArgs) -> Result<Option<Literal>, RuntimeError> + 'static,
+    {
+        return Self {
+            name,
+            params,
+            body: Rc::new(body),
+        };
+    }
+
+    /// Creates a native from an ordinary Rust function (or closure) of up to 4 arguments
+    /// The arguments are converted with `FromLiteral` and the result with `IntoLiteral`
+    ///
+    /// ### Panics
+    /// If the number of parameter names is different to the number of arguments of `func`
+    pub fn from_fn<F, Marker>(name: &'static str, params: &'static [&'static str], func: F) -> Self
+    where
+        F: IntoNativeBody<Marker>,
+    {
+        assert_eq!(
+            params.len(),
+            F::ARGS,
+            "native '{}' has {} parameter names for a function of {} arguments",
+            name,
+            params.len(),
+            F::ARGS
+        );
+        return Self {
+            name,
+            params,
+            body: func.into_body(),
+        };
+    }
+
+    fn has_rest(&self) -> bool {
+        return self
+            .params
+            .last()
+            .is_some_and(|param| param.starts_with("..."));
+    }
+}
+
+impl std::fmt::Debug for NativeFn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f
+            .debug_struct("NativeFn")
+            .field("name", &self.name)
+            .field("params", &self.params)
+            .finish();
+    }
+}
+
+impl QalamCallable for NativeFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let args = NativeArgs::init(&arguments, self.params, paren);
+        return self.body.call(interpreter, &args);
+    }
+
+    fn arity(&self) -> usize {
+        return self
+            .params
+            .iter()
+            .filter(|param| !param.ends_with('?') && !param.starts_with("..."))
+            .count();
+    }
+
+    fn max_arity(&self) -> Option<usize> {
+        if self.has_rest() {
+            return None;
+        }
+        return Some(self.params.len());
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return format!("<native amal {}({})>", self.name, self.params.join(", "));
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_identical(&self, other: &dyn QalamCallable) -> bool {
+        return match other.as_any().downcast_ref::<Self>() {
+            Some(other) => other.name == self.name,
+            None => false,
+        };
+    }
+}
+
+/// A Rust function that can be the body of a `NativeFn`
+/// `Marker` is the tuple of argument types, so functions of different arities don't conflict
+pub trait IntoNativeBody<Marker> {
+    /// Number of arguments the function takes
+    const ARGS: usize;
+
+    fn into_body(self) -> Rc<dyn NativeBody>;
+}
+
+/// A Rust function whose arguments and result are converted, see `NativeFn::from_fn`
+struct TypedBody<F, Marker> {
+    func: F,
+    marker: PhantomData<fn() -> Marker>,
+}
+
+macro_rules! impl_into_native_body {
+    ($count:expr $(, $arg:ident $index:expr)*) => {
+        impl<F, R $(, $arg)*> IntoNativeBody<($($arg,)*)> for F
+        where
+            F: Fn($($arg),*) -> R + 'static,
+            R: IntoLiteral + 'static,
+            $($arg: FromLiteral + 'static,)*
+        {
+            const ARGS: usize = $count;
+
+            fn into_body(self) -> Rc<dyn NativeBody> {
+                return Rc::new(TypedBody {
+                    func: self,
+                    marker: PhantomData,
+                });
+            }
+        }
+
+        impl<F, R $(, $arg)*> NativeBody for TypedBody<F, ($($arg,)*)>
+        where
+            F: Fn($($arg),*) -> R,
+            R: IntoLiteral,
+            $($arg: FromLiteral,)*
+        {
+            #[allow(unused_variables)]
+            fn call(&self, interpreter: &mut Interpreter, args: &NativeArgs) -> Result<Option<Literal>, RuntimeError> {
+                let result = (self.func)($(args.get::<$arg>($index)?),*);
+                return result.into_literal(interpreter, args.paren);
+            }
+        }
+    };
+}
+
+impl_into_native_body!(0);
+impl_into_native_body!(1, A 0);
+impl_into_native_body!(2, A 0, B 1);
+impl_into_native_body!(3, A 0, B 1, C 2);
+impl_into_native_body!(4, A 0, B 1, C 2, D 3);
@@ -54,6 +54,10 @@ impl QalamCallable for NumFn {
         return String::from("<native amal str2num(arg)>");
     }
 
+    fn name(&self) -> String {
+        String::from("str2num")
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -40,6 +40,10 @@ impl QalamCallable for PopFn {
         return "<native amal pop(arr)>".to_string();
     }
 
+    fn name(&self) -> String {
+        String::from("pop")
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         return self;
     }
@@ -45,6 +45,10 @@ impl QalamCallable for PowFn {
         return String::from("<native amal pow(base, exp)>");
     }
 
+    fn name(&self) -> String {
+        String::from("pow")
+    }
+
     fn clone_box(&self) -> Box<dyn QalamCallable> {
         return Box::new(self.clone());
     }
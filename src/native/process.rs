@@ -0,0 +1,127 @@
+use crate::native::convert::NativeArgs;
+use crate::native::*;
+use std::io::{BufRead, Write};
+
+#[derive(Debug, Clone)]
+pub struct PuchoFn {}
+
+impl PuchoFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for PuchoFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let prompt = match arguments.first() {
+            Some(Some(Literal::String(prompt))) => prompt.to_string(),
+            Some(None) | None => String::new(),
+            Some(value) => Literal::option_string(value.clone()),
+        };
+        // The prompt doesn't end with a new line, so it has to be flushed to be seen
+        let _ = write!(interpreter.output, "{}", prompt);
+        let _ = interpreter.output.flush();
+
+        let mut line = String::new();
+        let read = match interpreter.input.as_mut() {
+            Some(input) => input.read_line(&mut line),
+            None => std::io::stdin().read_line(&mut line),
+        };
+        let read = read.map_err(|e| {
+            RuntimeError::init(paren, format!("Cannot read input: {}.", e))
+        })?;
+        if read == 0 {
+            // End of input
+            return Ok(None);
+        }
+        if line.ends_with('\n') {
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+        interpreter.allocate(line.len(), paren)?;
+        return Ok(Some(Literal::String(line)));
+    }
+
+    fn arity(&self) -> usize {
+        return 0;
+    }
+
+    fn max_arity(&self) -> Option<usize> {
+        return Some(1);
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal pucho(prompt = \"\")>");
+    }
+
+    fn name(&self) -> String {
+        String::from("pucho")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExitFn {}
+
+impl ExitFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for ExitFn {
+    fn call(
+        &mut self,
+        _interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let status: Option<i64> = NativeArgs::init(&arguments, &["status?"], paren).get(0)?;
+        let status = status.unwrap_or(0);
+        if !(0..=255).contains(&status) {
+            return Err(RuntimeError::init(
+                paren,
+                String::from("'status' must be between 0 and 255!"),
+            ));
+        }
+        return Err(RuntimeError::init_exit(paren, status as i32));
+    }
+
+    fn arity(&self) -> usize {
+        return 0;
+    }
+
+    fn max_arity(&self) -> Option<usize> {
+        return Some(1);
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal exit(status = 0)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("exit")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
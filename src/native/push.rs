@@ -12,13 +12,14 @@ impl PushFn {
 impl QalamCallable for PushFn {
     fn call(
         &mut self,
-        _interpreter: &mut Interpreter,
+        interpreter: &mut Interpreter,
         arguments: Vec<Option<Literal>>,
         paren: &Token,
     ) -> Result<Option<Literal>, RuntimeError> {
         let arr = &arguments[0];
         let val = &arguments[1];
         if let Some(Literal::Array(arr)) = arr {
+            interpreter.allocate_elements(1, paren)?;
             arr.0.as_ref().borrow_mut().elements.push(val.clone());
             return Ok(None);
         } else {
@@ -37,6 +38,10 @@ impl QalamCallable for PushFn {
         return "<native amal push(arr, val)>".to_string();
     }
 
+    fn name(&self) -> String {
+        String::from("push")
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         return self;
     }
@@ -1,3 +1,4 @@
+use crate::native::convert::NativeArgs;
 use crate::native::*;
 
 #[derive(Debug, Clone)]
@@ -12,35 +13,24 @@ impl RandomFn {
 impl QalamCallable for RandomFn {
     fn call(
         &mut self,
-        _interpreter: &mut Interpreter,
+        interpreter: &mut Interpreter,
         arguments: Vec<Option<Literal>>,
         paren: &Token,
     ) -> Result<Option<Literal>, RuntimeError> {
-        let min = &arguments[0];
-        let max = &arguments[1];
-        if let (Some(min), Some(max)) = (min, max) {
-            if let (Literal::Number(min), Literal::Number(max)) = (min, max) {
-                if min > max {
-                    return Err(RuntimeError::init(
-                        paren,
-                        format!("'max' must be greater than or equal to 'min'"),
-                    ));
-                }
-                let mut rng = rand::thread_rng();
-                let res: f64 = rng.gen_range(**min..**max);
-                return Ok(Some(Literal::Number(OrderedFloat(res))));
-            } else {
-                return Err(RuntimeError::init(
-                    paren,
-                    format!("All arguments must be numbers!"),
-                ));
-            }
-        } else {
+        let args = NativeArgs::init(&arguments, &["min", "max"], paren);
+        let min: f64 = args.get(0)?;
+        let max: f64 = args.get(1)?;
+        if min > max {
             return Err(RuntimeError::init(
                 paren,
-                format!("All arguments must be defined!"),
+                String::from("'max' must be greater than or equal to 'min'"),
             ));
         }
+        if min == max {
+            return Ok(Some(Literal::Number(OrderedFloat(min))));
+        }
+        let res: f64 = interpreter.rng.gen_range(min..max);
+        return Ok(Some(Literal::Number(OrderedFloat(res))));
     }
 
     fn arity(&self) -> usize {
@@ -55,6 +45,10 @@ impl QalamCallable for RandomFn {
         return String::from("<native amal random(min, max)>");
     }
 
+    fn name(&self) -> String {
+        String::from("random")
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -1,3 +1,4 @@
+use crate::native::convert::NativeArgs;
 use crate::native::*;
 
 #[derive(Debug, Clone)]
@@ -12,50 +13,24 @@ impl RandomIntFn {
 impl QalamCallable for RandomIntFn {
     fn call(
         &mut self,
-        _interpreter: &mut Interpreter,
+        interpreter: &mut Interpreter,
         arguments: Vec<Option<Literal>>,
         paren: &Token,
     ) -> Result<Option<Literal>, RuntimeError> {
-        let min = &arguments[0];
-        let max = &arguments[1];
-        if let (Some(min), Some(max)) = (min, max) {
-            if let (Literal::Number(min), Literal::Number(max)) = (min, max) {
-                if !is_int(**min) {
-                    return Err(RuntimeError::init(
-                        paren,
-                        format!("'min' must be an integer!"),
-                    ));
-                }
-
-                if !is_int(**max) {
-                    return Err(RuntimeError::init(
-                        paren,
-                        format!("'max' must be an integer!"),
-                    ));
-                }
-                let min = **min as i32;
-                let max = **max as i32;
-                if min > max {
-                    return Err(RuntimeError::init(
-                        paren,
-                        format!("'max' must be greater than or equal to 'min'"),
-                    ));
-                }
-                let mut rng = rand::thread_rng();
-                let res: i32 = rng.gen_range(min..max);
-                return Ok(Some(Literal::Number(OrderedFloat(res as f64))));
-            } else {
-                return Err(RuntimeError::init(
-                    paren,
-                    format!("All arguments must be numbers!"),
-                ));
-            }
-        } else {
+        let args = NativeArgs::init(&arguments, &["min", "max"], paren);
+        let min: i64 = args.get(0)?;
+        let max: i64 = args.get(1)?;
+        if min > max {
             return Err(RuntimeError::init(
                 paren,
-                format!("All arguments must be defined!"),
+                String::from("'max' must be greater than or equal to 'min'"),
             ));
         }
+        if min == max {
+            return Ok(Some(Literal::Number(OrderedFloat(min as f64))));
+        }
+        let res: i64 = interpreter.rng.gen_range(min..max);
+        return Ok(Some(Literal::Number(OrderedFloat(res as f64))));
     }
 
     fn arity(&self) -> usize {
@@ -70,6 +45,10 @@ impl QalamCallable for RandomIntFn {
         return String::from("<native amal random_int(min, max)>");
     }
 
+    fn name(&self) -> String {
+        String::from("random_int")
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
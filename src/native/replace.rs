@@ -12,7 +12,7 @@ impl ReplaceFn {
 impl QalamCallable for ReplaceFn {
     fn call(
         &mut self,
-        _interpreter: &mut Interpreter,
+        interpreter: &mut Interpreter,
         arguments: Vec<Option<Literal>>,
         paren: &Token,
     ) -> Result<Option<Literal>, RuntimeError> {
@@ -28,6 +28,7 @@ impl QalamCallable for ReplaceFn {
             ) = (arg, old_substr, new_substr)
             {
                 let res = arg.replace(old_substr, &new_substr);
+                interpreter.allocate(res.len(), paren)?;
                 return Ok(Some(Literal::String(res)));
             } else {
                 return Err(RuntimeError::init(
@@ -55,6 +56,10 @@ impl QalamCallable for ReplaceFn {
         return String::from("<native amal replace(arg, old_substr, new_substr)>");
     }
 
+    fn name(&self) -> String {
+        String::from("replace")
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -32,7 +32,11 @@ impl QalamCallable for RoundFn {
     }
 
     fn to_string(&self) -> String {
-        return String::from("<native amal floor(num)>");
+        return String::from("<native amal round(num)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("round")
     }
 
     fn clone_box(&self) -> Box<dyn QalamCallable> {
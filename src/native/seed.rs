@@ -0,0 +1,15 @@
+use crate::native::convert::NativeArgs;
+use crate::native::native_fn::NativeFn;
+use crate::native::*;
+
+/// `seed(n)`, restarts the random numbers so the rest of the program gets the same ones on every run
+pub fn seed_fn() -> NativeFn {
+    return NativeFn::init("seed", &["n"], seed);
+}
+
+fn seed(interpreter: &mut Interpreter, args: &NativeArgs) -> Result<Option<Literal>, RuntimeError> {
+    let n: i64 = args.get(0)?;
+    // Negative seeds are as good as positive ones, their bits are used as they are
+    interpreter.seed(n as u64);
+    return Ok(None);
+}
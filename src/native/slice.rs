@@ -1,6 +1,6 @@
 use crate::hashable::HashableRcRefCell;
 use crate::literal::QalamArray;
-use crate::native::is_usize;
+use crate::native::convert::NativeArgs;
 use crate::native::*;
 
 #[derive(Debug, Clone)]
@@ -15,59 +15,30 @@ impl SliceFn {
 impl QalamCallable for SliceFn {
     fn call(
         &mut self,
-        _interpreter: &mut Interpreter,
+        interpreter: &mut Interpreter,
         arguments: Vec<Option<Literal>>,
         paren: &Token,
     ) -> Result<Option<Literal>, RuntimeError> {
-        let arr = &arguments[0];
-        let start = &arguments[1];
-        let end = &arguments[2];
-        if let Some(Literal::Array(arr)) = arr {
-            if let Some(Literal::Number(start)) = start {
-                if let Some(Literal::Number(end)) = end {
-                    if !is_usize(**start) {
-                        return Err(RuntimeError::init(
-                            paren,
-                            format!("'start' must be a positive integer!"),
-                        ));
-                    }
-
-                    if !is_usize(**end) {
-                        return Err(RuntimeError::init(
-                            paren,
-                            format!("'end' must be a positive integer!"),
-                        ));
-                    }
-
-                    if end < start {
-                        return Err(RuntimeError::init(
-                            paren,
-                            format!("'end' must be greater than or equal to 'start'."),
-                        ));
-                    }
-                    let slice = arr.0.as_ref().borrow().elements
-                        [(**start as usize)..(**end as usize)]
-                        .to_vec();
-                    let new_arr = QalamArray::from_vec(slice);
-                    return Ok(Some(Literal::Array(HashableRcRefCell::init(new_arr))));
-                } else {
-                    return Err(RuntimeError::init(
-                        paren,
-                        format!("'end' must be a number!"),
-                    ));
-                }
-            } else {
-                return Err(RuntimeError::init(
-                    paren,
-                    format!("'start' must be a number!"),
-                ));
-            }
-        } else {
+        let args = NativeArgs::init(&arguments, &["arr", "start", "end"], paren);
+        let arr: HashableRcRefCell<QalamArray> = args.get(0)?;
+        let start: usize = args.get(1)?;
+        let end: usize = args.get(2)?;
+        if end < start {
+            return Err(RuntimeError::init(
+                paren,
+                String::from("'end' must be greater than or equal to 'start'."),
+            ));
+        }
+        if end > arr.0.borrow().elements.len() {
             return Err(RuntimeError::init(
                 paren,
-                format!("'arr' must be an array!"),
+                String::from("'end' is out of range!"),
             ));
         }
+        interpreter.allocate_elements(end - start, paren)?;
+        let slice = arr.0.as_ref().borrow().elements[start..end].to_vec();
+        let new_arr = QalamArray::from_vec(slice);
+        return Ok(Some(Literal::Array(HashableRcRefCell::init(new_arr))));
     }
 
     fn arity(&self) -> usize {
@@ -78,6 +49,10 @@ impl QalamCallable for SliceFn {
         return "<native amal slice(arr, start, end)>".to_string();
     }
 
+    fn name(&self) -> String {
+        String::from("slice")
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         return self;
     }
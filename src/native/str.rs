@@ -32,6 +32,10 @@ impl QalamCallable for StrFn {
         return String::from("<native amal str(arg)>");
     }
 
+    fn name(&self) -> String {
+        String::from("str")
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -0,0 +1,560 @@
+use crate::hashable::HashableRcRefCell;
+use crate::literal::QalamArray;
+use crate::native::array::new_array;
+use crate::native::convert::{IntoLiteral, NativeArgs};
+use crate::native::*;
+
+/// A function that makes a new string from a string, e.g. `upper(s)`
+#[derive(Debug, Clone)]
+pub struct StringMapFn {
+    name: &'static str,
+    func: fn(&str) -> String,
+}
+
+impl StringMapFn {
+    pub fn init(name: &'static str, func: fn(&str) -> String) -> Self {
+        return Self { name, func };
+    }
+}
+
+impl QalamCallable for StringMapFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let s: String = NativeArgs::init(&arguments, &["s"], paren).get(0)?;
+        let result = (self.func)(&s);
+        interpreter.allocate(result.len(), paren)?;
+        return Ok(Some(Literal::String(result)));
+    }
+
+    fn arity(&self) -> usize {
+        return 1;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return format!("<native amal {}(s)>", self.name);
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_identical(&self, other: &dyn QalamCallable) -> bool {
+        return match other.as_any().downcast_ref::<Self>() {
+            Some(other) => other.name == self.name,
+            None => false,
+        };
+    }
+}
+
+/// A function that checks something about a string and another string, e.g. `starts_with(s, prefix)`
+#[derive(Debug, Clone)]
+pub struct StringTestFn {
+    name: &'static str,
+    /// Name of the second parameter
+    param: &'static str,
+    func: fn(&str, &str) -> bool,
+}
+
+impl StringTestFn {
+    pub fn init(name: &'static str, param: &'static str, func: fn(&str, &str) -> bool) -> Self {
+        return Self { name, param, func };
+    }
+}
+
+impl QalamCallable for StringTestFn {
+    fn call(
+        &mut self,
+        _interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let params = ["s", self.param];
+        let args = NativeArgs::init(&arguments, &params, paren);
+        let s: String = args.get(0)?;
+        let other: String = args.get(1)?;
+        return Ok(Some(Literal::Bool((self.func)(&s, &other))));
+    }
+
+    fn arity(&self) -> usize {
+        return 2;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return format!("<native amal {}(s, {})>", self.name, self.param);
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_identical(&self, other: &dyn QalamCallable) -> bool {
+        return match other.as_any().downcast_ref::<Self>() {
+            Some(other) => other.name == self.name,
+            None => false,
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SplitFn {}
+
+impl SplitFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for SplitFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let args = NativeArgs::init(&arguments, &["s", "separator"], paren);
+        let s: String = args.get(0)?;
+        let separator: String = args.get(1)?;
+        // An empty separator splits between every character
+        let parts: Vec<String> = match separator.is_empty() {
+            true => s.chars().map(|c| c.to_string()).collect(),
+            false => s
+                .split(separator.as_str())
+                .map(|part| part.to_string())
+                .collect(),
+        };
+        return parts.into_literal(interpreter, paren);
+    }
+
+    fn arity(&self) -> usize {
+        return 2;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal split(s, separator)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("split")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JoinFn {}
+
+impl JoinFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for JoinFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let args = NativeArgs::init(&arguments, &["arr", "separator"], paren);
+        let arr: HashableRcRefCell<QalamArray> = args.get(0)?;
+        let separator: String = args.get(1)?;
+        let parts: Vec<String> = arr
+            .0
+            .borrow()
+            .elements
+            .iter()
+            .map(|element| Literal::option_string(element.clone()))
+            .collect();
+        let result = parts.join(&separator);
+        interpreter.allocate(result.len(), paren)?;
+        return Ok(Some(Literal::String(result)));
+    }
+
+    fn arity(&self) -> usize {
+        return 2;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal join(arr, separator)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("join")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ContainsFn {}
+
+impl ContainsFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for ContainsFn {
+    fn call(
+        &mut self,
+        _interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        if let Some(Literal::Array(arr)) = &arguments[0] {
+            let found = arr.0.borrow().elements.contains(&arguments[1]);
+            return Ok(Some(Literal::Bool(found)));
+        }
+        let args = NativeArgs::init(&arguments, &["arg", "val"], paren);
+        let s: String = args.get(0)?;
+        let substring: String = args.get(1)?;
+        return Ok(Some(Literal::Bool(s.contains(substring.as_str()))));
+    }
+
+    fn arity(&self) -> usize {
+        return 2;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal contains(arg, val)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("contains")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RepeatFn {}
+
+impl RepeatFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for RepeatFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let args = NativeArgs::init(&arguments, &["s", "count"], paren);
+        let s: String = args.get(0)?;
+        let count: usize = args.get(1)?;
+        if s.is_empty() {
+            return Ok(Some(Literal::String(s)));
+        }
+        // Allocated before repeating, so a huge count is an error instead of running out of memory
+        let mut result = interpreter.string_with_capacity(s.len().saturating_mul(count), paren)?;
+        for _ in 0..count {
+            result.push_str(&s);
+        }
+        return Ok(Some(Literal::String(result)));
+    }
+
+    fn arity(&self) -> usize {
+        return 2;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal repeat(s, count)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("repeat")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PadFn {
+    /// Pad after the string instead of before it
+    end: bool,
+}
+
+impl PadFn {
+    pub fn init(end: bool) -> Self {
+        return Self { end };
+    }
+}
+
+impl QalamCallable for PadFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let args = NativeArgs::init(&arguments, &["s", "length", "fill"], paren);
+        let s: String = args.get(0)?;
+        let length: usize = args.get(1)?;
+        let fill: String = args.get(2)?;
+        if fill.is_empty() {
+            return Err(RuntimeError::init(
+                paren,
+                String::from("'fill' must not be empty!"),
+            ));
+        }
+
+        // Lengths are counted in characters, not bytes
+        let missing = length.saturating_sub(s.chars().count());
+        let fill_chars = fill.chars().count();
+        let partial: usize = fill.chars().take(missing % fill_chars).map(char::len_utf8).sum();
+        let padding_bytes = (missing / fill_chars)
+            .saturating_mul(fill.len())
+            .saturating_add(partial);
+        let bytes = s.len().saturating_add(padding_bytes);
+        let mut result = interpreter.string_with_capacity(bytes, paren)?;
+        let padding = fill.chars().cycle().take(missing);
+        match self.end {
+            true => {
+                result.push_str(&s);
+                result.extend(padding);
+            }
+            false => {
+                result.extend(padding);
+                result.push_str(&s);
+            }
+        }
+        return Ok(Some(Literal::String(result)));
+    }
+
+    fn arity(&self) -> usize {
+        return 3;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return match self.end {
+            true => String::from("<native amal pad_end(s, length, fill)>"),
+            false => String::from("<native amal pad_start(s, length, fill)>"),
+        };
+    }
+
+    fn name(&self) -> String {
+        match self.end {
+            true => String::from("pad_end"),
+            false => String::from("pad_start"),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn is_identical(&self, other: &dyn QalamCallable) -> bool {
+        return match other.as_any().downcast_ref::<Self>() {
+            Some(other) => other.end == self.end,
+            None => false,
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReverseFn {}
+
+impl ReverseFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for ReverseFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        if let Some(Literal::Array(arr)) = &arguments[0] {
+            let elements = arr.0.borrow().elements.iter().rev().cloned().collect();
+            return new_array(elements, interpreter, paren);
+        }
+        let s: String = NativeArgs::init(&arguments, &["arg"], paren).get(0)?;
+        interpreter.allocate(s.len(), paren)?;
+        return Ok(Some(Literal::String(s.chars().rev().collect())));
+    }
+
+    fn arity(&self) -> usize {
+        return 1;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal reverse(arg)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("reverse")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CharsFn {}
+
+impl CharsFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for CharsFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let s: String = NativeArgs::init(&arguments, &["s"], paren).get(0)?;
+        let chars: Vec<String> = s.chars().map(|c| c.to_string()).collect();
+        return chars.into_literal(interpreter, paren);
+    }
+
+    fn arity(&self) -> usize {
+        return 1;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal chars(s)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("chars")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FormatFn {}
+
+impl FormatFn {
+    pub fn init() -> Self {
+        return Self {};
+    }
+}
+
+impl QalamCallable for FormatFn {
+    fn call(
+        &mut self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<Option<Literal>>,
+        paren: &Token,
+    ) -> Result<Option<Literal>, RuntimeError> {
+        let template: String =
+            NativeArgs::init(&arguments, &["template", "...values"], paren).get(0)?;
+        let values = &arguments[1..];
+        let pieces: Vec<&str> = template.split("{}").collect();
+        if pieces.len() - 1 != values.len() {
+            return Err(RuntimeError::init(
+                paren,
+                format!(
+                    "'template' has {} placeholder(s) but got {} value(s)!",
+                    pieces.len() - 1,
+                    values.len()
+                ),
+            ));
+        }
+
+        let mut res = String::from(pieces[0]);
+        for (value, piece) in values.iter().zip(pieces[1..].iter()) {
+            match value {
+                Some(Literal::String(s)) => res.push_str(s),
+                value => res.push_str(&Literal::option_string(value.clone())),
+            }
+            res.push_str(piece);
+        }
+        interpreter.allocate(res.len(), paren)?;
+        return Ok(Some(Literal::String(res)));
+    }
+
+    fn arity(&self) -> usize {
+        return 1;
+    }
+
+    fn max_arity(&self) -> Option<usize> {
+        return None;
+    }
+
+    fn clone_box(&self) -> Box<dyn QalamCallable> {
+        return Box::new(self.clone());
+    }
+
+    fn to_string(&self) -> String {
+        return String::from("<native amal format(template, ...values)>");
+    }
+
+    fn name(&self) -> String {
+        String::from("format")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -78,6 +78,10 @@ impl QalamCallable for SubstrFn {
         return String::from("<native amal substr(arg, start, length)>");
     }
 
+    fn name(&self) -> String {
+        String::from("substr")
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -16,20 +16,7 @@ impl QalamCallable for TypeofFn {
         arguments: Vec<Option<Literal>>,
         _paren: &Token,
     ) -> Result<Option<Literal>, RuntimeError> {
-        let arg = &arguments[0];
-        match arg {
-            Some(arg) => match arg {
-                Literal::Bool(_) => Ok(Some(Literal::String(String::from("bool")))),
-                Literal::Number(_) => Ok(Some(Literal::String(String::from("number")))),
-                Literal::String(_) => Ok(Some(Literal::String(String::from("string")))),
-                Literal::Callable(_) => Ok(Some(Literal::String(String::from("amal")))),
-                Literal::Instance(instance) => {
-                    Ok(Some(Literal::String(instance.0.borrow().to_string())))
-                }
-                Literal::Array(_) => Ok(Some(Literal::String(String::from("array")))),
-            },
-            None => return Ok(Some(Literal::String(String::from("ghaib")))),
-        }
+        return Ok(Some(Literal::String(Literal::type_name(&arguments[0]))));
     }
 
     fn arity(&self) -> usize {
@@ -44,6 +31,10 @@ impl QalamCallable for TypeofFn {
         return String::from("<native amal typeof(arg)>");
     }
 
+    fn name(&self) -> String {
+        String::from("typeof")
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
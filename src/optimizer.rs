@@ -0,0 +1,418 @@
+use crate::ast::expr::Expr;
+use crate::ast::stmt::Stmt;
+use crate::ast::visitor::expr::ExprVisitor;
+use crate::ast::visitor::stmt::StmtVisitor;
+use crate::literal::Literal;
+use crate::token::{Token, TokenType};
+
+/// Optimises the AST before it is resolved and interpreted
+///
+/// - Folds arithmetic, comparisons and string concatenation of constants
+/// - Removes `agar` branches and `jabtak` loops that can never run
+/// - Removes code after `wapis`
+/// - Flattens blocks that don't declare anything into their parent
+///
+/// Anything that would cause a runtime error (e.g. dividing by zero) is left as is,
+/// so errors are still reported at the same place.
+pub struct Optimizer {}
+
+impl Optimizer {
+    pub fn init() -> Self {
+        return Self {};
+    }
+
+    /// Entry function
+    pub fn optimize(&mut self, mut statements: Vec<Stmt>) -> Vec<Stmt> {
+        return self.optimize_stmts(&mut statements);
+    }
+
+    fn optimize_expr(&mut self, expr: &Expr) -> Expr {
+        expr.accept(self)
+    }
+
+    fn optimize_stmt(&mut self, stmt: &mut Stmt) -> Vec<Stmt> {
+        stmt.accept(self)
+    }
+
+    /// Optimises a list of statements that run in the same scope
+    fn optimize_stmts(&mut self, statements: &mut [Stmt]) -> Vec<Stmt> {
+        let mut optimized = Vec::new();
+        for stmt in statements.iter_mut() {
+            for stmt in self.optimize_stmt(stmt) {
+                match stmt {
+                    Stmt::Block { statements } if !Self::declares(&statements) => {
+                        optimized.extend(statements);
+                    }
+                    _ => optimized.push(stmt),
+                }
+            }
+
+            // Everything after a return is unreachable
+            if let Some(Stmt::Return { .. }) = optimized.last() {
+                break;
+            }
+        }
+        return optimized;
+    }
+
+    /// Wraps optimised statements back into a single statement
+    fn single(mut statements: Vec<Stmt>) -> Stmt {
+        if statements.len() == 1 {
+            return statements.pop().unwrap();
+        }
+        return Stmt::Block { statements };
+    }
+
+    /// Checks if any of the statements declare a name in their scope
+    fn declares(statements: &[Stmt]) -> bool {
+        return statements.iter().any(|stmt| {
+            matches!(
+                stmt,
+                Stmt::Var { .. } | Stmt::Function { .. } | Stmt::Class { .. }
+            )
+        });
+    }
+
+    /// Gets the value of an expression if it is a constant
+    fn constant(expr: &Expr) -> Option<&Option<Literal>> {
+        match expr {
+            Expr::Literal { value } => Some(value),
+            _ => None,
+        }
+    }
+
+    fn is_truthy(value: &Option<Literal>) -> bool {
+        match value {
+            Some(Literal::Bool(val)) => *val,
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    /// Folds a binary operation on two constants
+    /// Returns `None` if it can't be folded or would cause a runtime error
+    fn fold_binary(
+        left: &Option<Literal>,
+        operator: &Token,
+        right: &Option<Literal>,
+    ) -> Option<Option<Literal>> {
+        match operator.token_type {
+            TokenType::EqualEqual => return Some(Some(Literal::Bool(left == right))),
+            TokenType::BangEqual => return Some(Some(Literal::Bool(left != right))),
+            _ => {}
+        }
+
+        if let (Some(Literal::Number(left)), Some(Literal::Number(right))) = (left, right) {
+            let (left, right) = (*left, *right);
+            let value = match operator.token_type {
+                TokenType::Plus => Literal::Number(left + right),
+                TokenType::Minus => Literal::Number(left - right),
+                TokenType::Star => Literal::Number(left * right),
+                TokenType::Slash if right != 0.0 => Literal::Number(left / right),
+                TokenType::Modulo if right != 0.0 => Literal::Number(left % right),
+                TokenType::Greater => Literal::Bool(left > right),
+                TokenType::GreaterEqual => Literal::Bool(left >= right),
+                TokenType::Less => Literal::Bool(left < right),
+                TokenType::LessEqual => Literal::Bool(left <= right),
+                _ => return None,
+            };
+            return Some(Some(value));
+        }
+
+        if let (Some(Literal::String(left)), Some(Literal::String(right))) = (left, right) {
+            if operator.token_type == TokenType::Plus {
+                return Some(Some(Literal::String(left.to_owned() + right)));
+            }
+        }
+
+        return None;
+    }
+}
+
+impl ExprVisitor for Optimizer {
+    type R = Expr;
+
+    fn visit_assign(&mut self, name: &Token, value: &Box<Expr>) -> Self::R {
+        return Expr::Assign {
+            name: name.clone(),
+            value: Box::new(self.optimize_expr(value)),
+        };
+    }
+
+    fn visit_binary(&mut self, left: &Box<Expr>, operator: &Token, right: &Box<Expr>) -> Self::R {
+        let left = self.optimize_expr(left);
+        let right = self.optimize_expr(right);
+        if let (Some(left_val), Some(right_val)) = (Self::constant(&left), Self::constant(&right)) {
+            if let Some(value) = Self::fold_binary(left_val, operator, right_val) {
+                return Expr::Literal { value };
+            }
+        }
+
+        return Expr::Binary {
+            left: Box::new(left),
+            operator: operator.clone(),
+            right: Box::new(right),
+        };
+    }
+
+    fn visit_grouping(&mut self, expression: &Box<Expr>) -> Self::R {
+        let expression = self.optimize_expr(expression);
+        if let Expr::Literal { .. } = expression {
+            return expression;
+        }
+        return Expr::Grouping {
+            expression: Box::new(expression),
+        };
+    }
+
+    fn visit_literal(&mut self, expr: &Option<Literal>) -> Self::R {
+        return Expr::Literal {
+            value: expr.clone(),
+        };
+    }
+
+    fn visit_unary(&mut self, operator: &Token, right: &Box<Expr>) -> Self::R {
+        let right = self.optimize_expr(right);
+        if let Some(value) = Self::constant(&right) {
+            match (operator.token_type, value) {
+                (TokenType::Minus, Some(Literal::Number(num))) => {
+                    return Expr::Literal {
+                        value: Some(Literal::Number(-*num)),
+                    };
+                }
+                (TokenType::Bang, value) => {
+                    return Expr::Literal {
+                        value: Some(Literal::Bool(!Self::is_truthy(value))),
+                    };
+                }
+                _ => {}
+            }
+        }
+
+        return Expr::Unary {
+            operator: operator.clone(),
+            right: Box::new(right),
+        };
+    }
+
+    fn visit_variable(&mut self, name: &Token) -> Self::R {
+        return Expr::Variable { name: name.clone() };
+    }
+
+    fn visit_logical(&mut self, left: &Box<Expr>, operator: &Token, right: &Box<Expr>) -> Self::R {
+        let left = self.optimize_expr(left);
+        let right = self.optimize_expr(right);
+        if let Some(value) = Self::constant(&left) {
+            // `ya` returns the left side if it is truthy, `aur` if it is falsy
+            let short_circuits = match operator.token_type {
+                TokenType::Or => Self::is_truthy(value),
+                _ => !Self::is_truthy(value),
+            };
+            if short_circuits {
+                return left;
+            }
+            return right;
+        }
+
+        return Expr::Logical {
+            left: Box::new(left),
+            operator: operator.clone(),
+            right: Box::new(right),
+        };
+    }
+
+    fn visit_call(&mut self, callee: &Box<Expr>, paren: &Token, arguments: &Vec<Expr>) -> Self::R {
+        return Expr::Call {
+            callee: Box::new(self.optimize_expr(callee)),
+            paren: paren.clone(),
+            arguments: arguments
+                .iter()
+                .map(|arg| self.optimize_expr(arg))
+                .collect(),
+        };
+    }
+
+    fn visit_get(&mut self, object: &Box<Expr>, name: &Token) -> Self::R {
+        return Expr::Get {
+            object: Box::new(self.optimize_expr(object)),
+            name: name.clone(),
+        };
+    }
+
+    fn visit_set(&mut self, object: &Box<Expr>, name: &Token, value: &Box<Expr>) -> Self::R {
+        return Expr::Set {
+            object: Box::new(self.optimize_expr(object)),
+            name: name.clone(),
+            value: Box::new(self.optimize_expr(value)),
+        };
+    }
+
+    fn visit_this(&mut self, keyword: &Token) -> Self::R {
+        return Expr::This {
+            keyword: keyword.clone(),
+        };
+    }
+
+    fn visit_super(&mut self, keyword: &Token, method: &Token) -> Self::R {
+        return Expr::Super {
+            keyword: keyword.clone(),
+            method: method.clone(),
+        };
+    }
+
+    fn visit_array(&mut self, values: &Vec<Expr>) -> Self::R {
+        return Expr::Array {
+            values: values.iter().map(|value| self.optimize_expr(value)).collect(),
+        };
+    }
+
+    fn visit_get_indexed(
+        &mut self,
+        object: &Box<Expr>,
+        index: &Box<Expr>,
+        bracket: &Token,
+    ) -> Self::R {
+        return Expr::GetIndexed {
+            object: Box::new(self.optimize_expr(object)),
+            index: Box::new(self.optimize_expr(index)),
+            bracket: bracket.clone(),
+        };
+    }
+
+    fn visit_set_indexed(
+        &mut self,
+        object: &Box<Expr>,
+        index: &Box<Expr>,
+        value: &Box<Expr>,
+        bracket: &Token,
+    ) -> Self::R {
+        return Expr::SetIndexed {
+            object: Box::new(self.optimize_expr(object)),
+            index: Box::new(self.optimize_expr(index)),
+            value: Box::new(self.optimize_expr(value)),
+            bracket: bracket.clone(),
+        };
+    }
+}
+
+impl StmtVisitor for Optimizer {
+    type R = Vec<Stmt>;
+
+    fn visit_expression(&mut self, expression: &Expr) -> Self::R {
+        return vec![Stmt::Expression {
+            expression: self.optimize_expr(expression),
+        }];
+    }
+
+    fn visit_print(&mut self, keyword: &Token, expression: &Expr) -> Self::R {
+        return vec![Stmt::Print {
+            keyword: keyword.clone(),
+            expression: self.optimize_expr(expression),
+        }];
+    }
+
+    fn visit_var(&mut self, name: &Token, initializer: &Option<Expr>) -> Self::R {
+        return vec![Stmt::Var {
+            name: name.clone(),
+            initializer: initializer.as_ref().map(|expr| self.optimize_expr(expr)),
+        }];
+    }
+
+    fn visit_block(&mut self, statements: &mut Vec<Stmt>) -> Self::R {
+        let statements = self.optimize_stmts(statements);
+        // A block that only holds another block is the same as the inner block
+        if statements.len() == 1 {
+            if let Some(Stmt::Block { .. }) = statements.first() {
+                return statements;
+            }
+        }
+        return vec![Stmt::Block { statements }];
+    }
+
+    fn visit_if(
+        &mut self,
+        condition: &Expr,
+        then: &mut Box<Stmt>,
+        else_branch: &mut Option<Box<Stmt>>,
+    ) -> Self::R {
+        let condition = self.optimize_expr(condition);
+        if let Some(value) = Self::constant(&condition) {
+            if Self::is_truthy(value) {
+                return self.optimize_stmt(then);
+            }
+            return match else_branch {
+                Some(else_branch) => self.optimize_stmt(else_branch),
+                None => Vec::new(),
+            };
+        }
+
+        let then = Self::single(self.optimize_stmt(then));
+        let else_branch = else_branch
+            .as_mut()
+            .map(|else_branch| Box::new(Self::single(self.optimize_stmt(else_branch))));
+        return vec![Stmt::If {
+            condition,
+            then: Box::new(then),
+            else_branch,
+        }];
+    }
+
+    fn visit_while(&mut self, condition: &Expr, body: &mut Box<Stmt>) -> Self::R {
+        let condition = self.optimize_expr(condition);
+        if let Some(value) = Self::constant(&condition) {
+            if !Self::is_truthy(value) {
+                return Vec::new();
+            }
+        }
+
+        let body = Self::single(self.optimize_stmt(body));
+        return vec![Stmt::While {
+            condition,
+            body: Box::new(body),
+        }];
+    }
+
+    fn visit_function(
+        &mut self,
+        name: &Token,
+        params: &Vec<Token>,
+        defaults: &[Option<Expr>],
+        rest: bool,
+        body: &mut Vec<Stmt>,
+    ) -> Self::R {
+        return vec![Stmt::Function {
+            name: name.clone(),
+            params: params.clone(),
+            defaults: defaults
+                .iter()
+                .map(|default| default.as_ref().map(|expr| self.optimize_expr(expr)))
+                .collect(),
+            rest,
+            body: self.optimize_stmts(body),
+        }];
+    }
+
+    fn visit_return(&mut self, keyword: &Token, value: &Option<Expr>) -> Self::R {
+        return vec![Stmt::Return {
+            keyword: keyword.clone(),
+            value: value.as_ref().map(|expr| self.optimize_expr(expr)),
+        }];
+    }
+
+    fn visit_class(
+        &mut self,
+        name: &Token,
+        methods: &mut Vec<Stmt>,
+        superclass: &Option<Expr>,
+    ) -> Self::R {
+        let mut optimized_methods = Vec::new();
+        for method in methods.iter_mut() {
+            optimized_methods.extend(self.optimize_stmt(method));
+        }
+        return vec![Stmt::Class {
+            name: name.clone(),
+            methods: optimized_methods,
+            superclass: superclass.clone(),
+        }];
+    }
+}
@@ -163,6 +163,7 @@ impl<'a> Parser<'a> {
                                 None,
                                 equals.line,
                                 equals.position,
+                                equals.column,
                             ),
                             right: Box::new(value),
                         }),
@@ -180,6 +181,7 @@ impl<'a> Parser<'a> {
                                 None,
                                 equals.line,
                                 equals.position,
+                                equals.column,
                             ),
                             right: Box::new(value),
                         }),
@@ -205,6 +207,7 @@ impl<'a> Parser<'a> {
                                 None,
                                 equals.line,
                                 equals.position,
+                                equals.column,
                             ),
                             right: Box::new(value),
                         }),
@@ -224,6 +227,7 @@ impl<'a> Parser<'a> {
                     None,
                     equals.line,
                     equals.position,
+                    equals.column,
                 ),
                 TokenType::Decrement => Token::init(
                     TokenType::Minus,
@@ -231,6 +235,7 @@ impl<'a> Parser<'a> {
                     None,
                     equals.line,
                     equals.position,
+                    equals.column,
                 ),
                 _ => {
                     // This will never happen, we already checked above
@@ -590,9 +595,13 @@ impl<'a> Parser<'a> {
     }
 
     fn print_stmt(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous().clone();
         let value = self.expression()?;
         self.consume(&TokenType::Semicolon, "Expect ';' after value.")?;
-        return Ok(Stmt::Print { expression: value });
+        return Ok(Stmt::Print {
+            keyword,
+            expression: value,
+        });
     }
 
     fn expression_stmt(&mut self) -> Result<Stmt, ParseError> {
@@ -769,6 +778,8 @@ impl<'a> Parser<'a> {
             &format!("Expect '(' after {} name.", kind),
         )?;
         let mut params = Vec::new();
+        let mut defaults = Vec::new();
+        let mut rest = false;
         if !self.check(&TokenType::RightParen) {
             loop {
                 if params.len() >= 255 {
@@ -778,9 +789,39 @@ impl<'a> Parser<'a> {
                     ));
                 }
 
-                params.push(Token::copy(
+                // A rest parameter is written `...name`
+                if self.match_types(&[TokenType::Dot]) {
+                    self.consume(&TokenType::Dot, "Expect '...' before rest parameter name.")?;
+                    self.consume(&TokenType::Dot, "Expect '...' before rest parameter name.")?;
+                    rest = true;
+                }
+                let param = Token::copy(
                     self.consume(&TokenType::Identifier, "Expect parameter name.")?,
-                ));
+                );
+                if rest {
+                    params.push(param);
+                    defaults.push(None);
+                    if self.check(&TokenType::Comma) {
+                        return Err(self.error(
+                            &Token::copy(self.peek()),
+                            "Rest parameter must be the last parameter.",
+                        ));
+                    }
+                    break;
+                }
+
+                let default = match self.match_types(&[TokenType::Equal]) {
+                    true => Some(self.expression()?),
+                    false => None,
+                };
+                if default.is_none() && defaults.iter().any(|default| default.is_some()) {
+                    return Err(self.error(
+                        &param,
+                        "Parameters after one with a default value must have a default value too.",
+                    ));
+                }
+                params.push(param);
+                defaults.push(default);
                 if !self.match_types(&[TokenType::Comma]) {
                     break;
                 }
@@ -793,7 +834,13 @@ impl<'a> Parser<'a> {
         )?;
 
         let body = self.block()?;
-        return Ok(Stmt::Function { name, params, body });
+        return Ok(Stmt::Function {
+            name,
+            params,
+            defaults,
+            rest,
+            body,
+        });
     }
 
     fn class_declaration(&mut self) -> Result<Stmt, ParseError> {
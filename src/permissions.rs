@@ -0,0 +1,119 @@
+use std::path::{Path, PathBuf};
+
+/// What a program wants to do with a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+impl Access {
+    /// The CLI flag that grants this access
+    pub fn flag(&self) -> &'static str {
+        match self {
+            Self::Read => "--allow-read",
+            Self::Write => "--allow-write",
+        }
+    }
+
+    fn verb(&self) -> &'static str {
+        match self {
+            Self::Read => "Reading",
+            Self::Write => "Writing",
+        }
+    }
+}
+
+/// Paths a program may access through the file system natives
+/// Everything is denied unless the path or a directory containing it is allowed
+#[derive(Debug, Clone, Default)]
+pub struct Permissions {
+    /// Canonical paths that can be read
+    pub read: Vec<PathBuf>,
+    /// Canonical paths that can be written
+    pub write: Vec<PathBuf>,
+}
+
+impl Permissions {
+    pub fn init() -> Self {
+        return Self {
+            read: Vec::new(),
+            write: Vec::new(),
+        };
+    }
+
+    /// Allows access to a path and everything inside it
+    ///
+    /// ### Returns
+    /// `Result<(), String>` - an error if the path doesn't exist
+    pub fn allow(&mut self, access: Access, path: &str) -> Result<(), String> {
+        let canonical = std::fs::canonicalize(path)
+            .map_err(|e| format!("Cannot allow {}={}: {}", access.flag(), path, e))?;
+        match access {
+            Access::Read => self.read.push(canonical),
+            Access::Write => self.write.push(canonical),
+        }
+        return Ok(());
+    }
+
+    /// Resolves a path the way the operating system would, following `..` and symbolic links
+    /// Paths that don't exist yet (e.g. a file about to be written) are resolved through their parent
+    fn canonicalize(path: &Path) -> Option<PathBuf> {
+        if let Ok(canonical) = std::fs::canonicalize(path) {
+            return Some(canonical);
+        }
+        // A symbolic link whose target doesn't exist can't be resolved, and writing through
+        // it would create the target wherever it points, so it isn't allowed
+        if std::fs::symlink_metadata(path).is_ok() {
+            return None;
+        }
+        let parent = match path.parent() {
+            Some(parent) if parent.as_os_str().is_empty() => Path::new("."),
+            Some(parent) => parent,
+            None => return None,
+        };
+        let name = path.file_name()?;
+        return Some(std::fs::canonicalize(parent).ok()?.join(name));
+    }
+
+    /// Checks that a path may be accessed
+    ///
+    /// ### Returns
+    /// `Result<PathBuf, String>` - the canonical path, or a message naming the flag that allows it
+    pub fn check(&self, access: Access, path: &str) -> Result<PathBuf, String> {
+        let allowed = match access {
+            Access::Read => &self.read,
+            Access::Write => &self.write,
+        };
+        let canonical = Self::canonicalize(Path::new(path));
+        if let Some(canonical) = canonical.as_ref() {
+            if allowed.iter().any(|dir| canonical.starts_with(dir)) {
+                return Ok(canonical.to_path_buf());
+            }
+        }
+
+        if canonical.is_none() && std::fs::symlink_metadata(path).is_ok() {
+            return Err(format!(
+                "{} '{}' is not allowed because it is a symbolic link to a path that doesn't exist.",
+                access.verb(),
+                path
+            ));
+        }
+
+        let suggestion = match canonical.as_ref() {
+            Some(canonical) if canonical.is_dir() => canonical.display().to_string(),
+            Some(canonical) => canonical
+                .parent()
+                .map(|parent| parent.display().to_string())
+                .unwrap_or_default(),
+            None => path.to_string(),
+        };
+        return Err(format!(
+            "{} '{}' is not allowed. Run with {}={} to allow it.",
+            access.verb(),
+            path,
+            access.flag(),
+            suggestion
+        ));
+    }
+}
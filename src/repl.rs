@@ -0,0 +1,179 @@
+use crate::environment::Environment;
+use crate::literal::Literal;
+use crate::methods::{MethodType, Methods};
+use crate::token::KEYWORDS;
+use colored::Colorize;
+use rustyline::completion::{Completer, Pair};
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Helper};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+pub const HELP: &str = "\
+Commands:
+  :help             Show this message
+  :load <file.qlm>  Run a file in this session
+  :reset            Forget everything defined in this session
+  :env              List the globals and their types
+  :type <expr>      Evaluate an expression and show the type of its value
+  :ast <expr>       Show the syntax tree of an expression or statement
+  exit()            Leave the REPL";
+
+/// Type of a value shown by `:env` and `:type`
+/// Namespaces like `math` are instances, but are shown as namespaces rather than `<instanceof math>`
+pub fn type_name(value: &Option<Literal>) -> String {
+    if let Some(Literal::Instance(instance)) = value {
+        if instance.0.borrow().namespace {
+            return String::from("namespace");
+        }
+    }
+    return Literal::type_name(value);
+}
+
+/// Where the REPL keeps its history across sessions
+///
+/// Set with the `QALAM_HISTORY` environment variable, `~/.qalam_history` by default
+pub fn history_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("QALAM_HISTORY") {
+        return Some(PathBuf::from(path));
+    }
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")).ok()?;
+    return Some(PathBuf::from(home).join(".qalam_history"));
+}
+
+/// Completes and highlights REPL input
+pub struct ReplHelper {
+    /// Globals of the session's interpreter, for completing names
+    pub globals: Rc<RefCell<Environment>>,
+    /// Names of the methods of strings, arrays and numbers, completed after a `.`
+    pub methods: Vec<String>,
+}
+
+impl ReplHelper {
+    pub fn init(globals: Rc<RefCell<Environment>>, methods: &Methods) -> Self {
+        let mut names = Vec::new();
+        for method_type in [MethodType::String, MethodType::Array, MethodType::Number] {
+            names.extend(methods.names(method_type));
+        }
+        names.sort();
+        names.dedup();
+        return Self {
+            globals,
+            methods: names,
+        };
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let start = line[..pos]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_alphanumeric() || *c == '_')
+            .last()
+            .map(|(i, _)| i)
+            .unwrap_or(pos);
+        let prefix = &line[start..pos];
+        if prefix.is_empty() {
+            return Ok((pos, Vec::new()));
+        }
+
+        let names = match line[..start].ends_with('.') {
+            // Fields of instances aren't known until the line runs, so only methods are completed
+            true => self.methods.clone(),
+            false => {
+                let mut names: Vec<String> =
+                    KEYWORDS.iter().map(|keyword| keyword.to_string()).collect();
+                names.extend(self.globals.borrow().iter().map(|(name, _)| name.to_string()));
+                names.sort();
+                names.dedup();
+                names
+            }
+        };
+        let candidates = names
+            .into_iter()
+            .filter(|name| name.starts_with(prefix))
+            .map(|name| Pair {
+                display: name.to_string(),
+                replacement: name,
+            })
+            .collect();
+        return Ok((start, candidates));
+    }
+}
+
+impl Highlighter for ReplHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        return Cow::Owned(highlight(line));
+    }
+
+    fn highlight_char(&self, _line: &str, _pos: usize) -> bool {
+        return true;
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+/// Colours the keywords, strings, numbers and comments of a line
+///
+/// Works on the characters directly rather than the scanner, since the line is
+/// usually unfinished while it's being typed.
+pub fn highlight(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut highlighted = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c == '/' && chars.get(i + 1) == Some(&'/') {
+            let comment: String = chars[i..].iter().collect();
+            highlighted.push_str(&comment.dimmed().to_string());
+            break;
+        } else if c == '"' {
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            let string: String = chars[start..i].iter().collect();
+            highlighted.push_str(&string.green().to_string());
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let number: String = chars[start..i].iter().collect();
+            highlighted.push_str(&number.yellow().to_string());
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if KEYWORDS.contains(&word.as_str()) {
+                highlighted.push_str(&word.magenta().bold().to_string());
+            } else {
+                highlighted.push_str(&word);
+            }
+        } else {
+            highlighted.push(c);
+            i += 1;
+        }
+    }
+    return highlighted;
+}
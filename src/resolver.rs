@@ -5,6 +5,7 @@ use crate::ast::visitor::stmt::StmtVisitor;
 use crate::error::RuntimeError;
 use crate::interpreter::Interpreter;
 use crate::stack::Stack;
+use crate::symbols::{SymbolKind, SymbolTable};
 use crate::token::Token;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -28,6 +29,8 @@ pub struct Resolver {
     scopes: Stack<HashMap<String, bool>>,
     current_function: Option<FunctionType>,
     current_class: Option<ClassType>,
+    /// Declarations and uses of names, only recorded for the linter and editor tooling
+    pub symbols: Option<SymbolTable>,
 }
 
 impl Resolver {
@@ -37,15 +40,44 @@ impl Resolver {
             scopes: Stack::new(),
             current_function: None,
             current_class: None,
+            symbols: None,
         };
     }
 
+    /// Creates a resolver that also records a `SymbolTable`
+    pub fn init_with_symbols(interpreter: Rc<RefCell<Interpreter>>) -> Self {
+        let mut resolver = Self::init(interpreter);
+        resolver.symbols = Some(SymbolTable::init());
+        return resolver;
+    }
+
     fn begin_scope(&mut self) {
         self.scopes.push(HashMap::new());
+        if let Some(symbols) = &mut self.symbols {
+            symbols.begin_scope();
+        }
     }
 
     fn end_scope(&mut self) {
         self.scopes.pop();
+        if let Some(symbols) = &mut self.symbols {
+            symbols.end_scope();
+        }
+    }
+
+    /// Records a declaration in the symbol table
+    fn record(&mut self, name: &Token, kind: SymbolKind, params: &[Token]) {
+        if let Some(symbols) = &mut self.symbols {
+            let params = params.iter().map(|param| param.lexeme.to_string()).collect();
+            symbols.declare(name, kind, params);
+        }
+    }
+
+    /// Records a use of a name in the symbol table
+    fn record_reference(&mut self, name: &Token, read: bool) {
+        if let Some(symbols) = &mut self.symbols {
+            symbols.reference(name, read);
+        }
     }
 
     fn resolve_stmt(&mut self, stmt: &mut Stmt) -> Result<(), RuntimeError> {
@@ -61,6 +93,11 @@ impl Resolver {
             self.resolve_stmt(stmt)?;
         }
 
+        if self.scopes.is_empty() {
+            if let Some(symbols) = &mut self.symbols {
+                symbols.finish();
+            }
+        }
         return Ok(());
     }
 
@@ -68,13 +105,19 @@ impl Resolver {
         &mut self,
         _name: &Token,
         params: &Vec<Token>,
+        defaults: &[Option<Expr>],
         body: &mut Vec<Stmt>,
         func_type: Option<FunctionType>,
     ) -> Result<(), RuntimeError> {
         let enclosing_func = self.current_function.clone();
         self.current_function = func_type;
         self.begin_scope();
-        for param in params.iter() {
+        for (param, default) in params.iter().zip(defaults.iter()) {
+            // Defaults are evaluated when the function is called, after the parameters before them are bound
+            if let Some(default) = default {
+                self.resolve_expr(default)?;
+            }
+            self.record(param, SymbolKind::Parameter, &Vec::new());
             self.declare(param.clone())?;
             self.define(param.clone())?;
         }
@@ -150,11 +193,14 @@ impl StmtVisitor for Resolver {
         &mut self,
         name: &Token,
         params: &Vec<Token>,
+        defaults: &[Option<Expr>],
+        _rest: bool,
         body: &mut Vec<Stmt>,
     ) -> Self::R {
+        self.record(name, SymbolKind::Function, params);
         self.declare(name.clone())?;
         self.define(name.clone())?;
-        self.resolve_function(name, params, body, Some(FunctionType::Function))?;
+        self.resolve_function(name, params, defaults, body, Some(FunctionType::Function))?;
         return Ok(());
     }
 
@@ -172,7 +218,7 @@ impl StmtVisitor for Resolver {
         return Ok(());
     }
 
-    fn visit_print(&mut self, expression: &Expr) -> Self::R {
+    fn visit_print(&mut self, _keyword: &Token, expression: &Expr) -> Self::R {
         self.resolve_expr(expression)?;
         return Ok(());
     }
@@ -203,6 +249,7 @@ impl StmtVisitor for Resolver {
         if let Some(initializer) = initializer {
             self.resolve_expr(initializer)?;
         }
+        self.record(name, SymbolKind::Variable, &Vec::new());
         self.define(name.clone())?;
         return Ok(());
     }
@@ -221,6 +268,7 @@ impl StmtVisitor for Resolver {
     ) -> Self::R {
         let enclosing_class = self.current_class.clone();
         self.current_class = Some(ClassType::Class);
+        self.record(name, SymbolKind::Class, &Vec::new());
         self.declare(name.clone())?;
         self.define(name.clone())?;
 
@@ -257,11 +305,18 @@ impl StmtVisitor for Resolver {
             .insert(String::from("yeh"), true);
         for method in methods.iter_mut() {
             let mut declaration = FunctionType::Method;
-            if let Stmt::Function { name, params, body } = method {
+            if let Stmt::Function {
+                name,
+                params,
+                defaults,
+                body,
+                ..
+            } = method
+            {
                 if name.lexeme.eq(&String::from("banao")) {
                     declaration = FunctionType::Initializer;
                 }
-                self.resolve_function(name, params, body, Some(declaration))?;
+                self.resolve_function(name, params, defaults, body, Some(declaration))?;
             } else {
                 return Err(RuntimeError::init(
                     name,
@@ -283,6 +338,7 @@ impl ExprVisitor for Resolver {
 
     fn visit_assign(&mut self, name: &Token, value: &Box<Expr>) -> Self::R {
         self.resolve_expr(value)?;
+        self.record_reference(name, false);
         self.resolve_local_expr(
             &Expr::Assign {
                 name: name.clone(),
@@ -342,6 +398,7 @@ impl ExprVisitor for Resolver {
             ));
         }
 
+        self.record_reference(name, true);
         self.resolve_local_expr(&Expr::Variable { name: name.clone() }, name)?;
         return Ok(());
     }
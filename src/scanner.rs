@@ -4,28 +4,48 @@ use crate::{ErrorReporter, ErrorType};
 use ordered_float::OrderedFloat;
 
 pub struct Scanner<'a> {
-    source: String,
+    source: Vec<char>,
     tokens: Vec<Token>,
     start: usize,
     current: usize,
     line: i64,
     position: i64,
+    /// Index of the first character of the current line
+    line_start: usize,
+    /// Column of the token being scanned
+    column: i64,
+    keep_comments: bool,
     error_reporter: &'a mut ErrorReporter,
 }
 
 impl<'a> Scanner<'a> {
-    pub fn init(source: &String, error_reporter: &'a mut ErrorReporter) -> Self {
+    pub fn init(source: &str, error_reporter: &'a mut ErrorReporter) -> Self {
         Self {
-            source: source.to_string(),
+            source: source.chars().collect(),
             tokens: Vec::new(),
             start: 0,
             current: 0,
             line: 1,
             position: 0,
+            line_start: 0,
+            column: 1,
+            keep_comments: false,
             error_reporter,
         }
     }
 
+    /// Creates a scanner that keeps comments as `Comment` tokens (used by the formatter)
+    pub fn init_with_comments(source: &str, error_reporter: &'a mut ErrorReporter) -> Self {
+        let mut scanner = Self::init(source, error_reporter);
+        scanner.keep_comments = true;
+        return scanner;
+    }
+
+    /// Gets the source text between two character indices
+    fn text(&self, start: usize, end: usize) -> Option<String> {
+        return self.source.get(start..end).map(|chars| chars.iter().collect());
+    }
+
     /// Checks if we have reached the end of the source string
     fn end(&self) -> bool {
         return self.current >= self.source.len();
@@ -36,7 +56,7 @@ impl<'a> Scanner<'a> {
     /// ### Returns
     /// `char` - the character at the current spot
     fn advance(&mut self) -> char {
-        let c = self.source.chars().nth(self.current);
+        let c = self.source.get(self.current).copied();
         self.current += 1;
         return match c {
             Some(c) => c,
@@ -58,7 +78,7 @@ impl<'a> Scanner<'a> {
             return false;
         }
 
-        match self.source.chars().nth(self.current) {
+        match self.source.get(self.current).copied() {
             Some(c) => {
                 if expected == c {
                     self.current += 1;
@@ -80,7 +100,7 @@ impl<'a> Scanner<'a> {
         if self.end() {
             return '\0';
         } else {
-            return match self.source.chars().nth(self.current) {
+            return match self.source.get(self.current).copied() {
                 Some(c) => c,
                 None => {
                     eprintln!("Cannot find character!");
@@ -97,7 +117,7 @@ impl<'a> Scanner<'a> {
         if self.current + 1 >= self.source.len() {
             return '\0';
         }
-        return match self.source.chars().nth(self.current + 1) {
+        return match self.source.get(self.current + 1).copied() {
             Some(c) => c,
             None => {
                 eprintln!("Cannot find character!");
@@ -111,7 +131,7 @@ impl<'a> Scanner<'a> {
     /// `token_type` - the type of the token
     /// `literal` - the object literal
     fn add_token(&mut self, token_type: TokenType, literal: Option<Literal>) {
-        let text = self.source.get(self.start..self.current);
+        let text = self.text(self.start, self.current);
         match text {
             Some(t) => {
                 self.tokens.push(Token::init(
@@ -120,6 +140,7 @@ impl<'a> Scanner<'a> {
                     literal,
                     self.line,
                     self.position,
+                    self.column,
                 ));
                 self.position += 1;
             }
@@ -143,6 +164,7 @@ impl<'a> Scanner<'a> {
                 // Supports multiline strings
                 self.line += 1;
                 self.position = 0;
+                self.line_start = self.current + 1;
             }
             self.advance();
         }
@@ -156,7 +178,7 @@ impl<'a> Scanner<'a> {
 
         // Advance to the ending quote
         self.advance();
-        let value = self.source.get((self.start + 1)..(self.current - 1));
+        let value = self.text(self.start + 1, self.current - 1);
         match value {
             Some(val) => self.add_token(TokenType::String, Some(Literal::String(val.to_string()))),
             None => {
@@ -212,7 +234,7 @@ impl<'a> Scanner<'a> {
             }
         }
 
-        let value = self.source.get(self.start..self.current);
+        let value = self.text(self.start, self.current);
         match value {
             Some(val) => {
                 let as_float = match val.parse::<f64>() {
@@ -241,8 +263,8 @@ impl<'a> Scanner<'a> {
             self.advance();
             peek = self.peek();
         }
-        match self.source.get(self.start..self.current) {
-            Some(text) => match TokenType::from_keyword(text) {
+        match self.text(self.start, self.current) {
+            Some(text) => match TokenType::from_keyword(&text) {
                 Some(token_type) => {
                     self.add_token(token_type, None);
                 }
@@ -331,6 +353,9 @@ impl<'a> Scanner<'a> {
                     while self.peek() != '\n' && !self.end() {
                         self.advance();
                     }
+                    if self.keep_comments {
+                        self.add_token(TokenType::Comment, None);
+                    }
                 } else if self.match_next('=') {
                     self.add_token(TokenType::SlashEqual, None);
                 } else {
@@ -351,6 +376,7 @@ impl<'a> Scanner<'a> {
             '\n' => {
                 self.line += 1;
                 self.position = 0;
+                self.line_start = self.current;
             }
             '"' => {
                 self.string();
@@ -375,6 +401,7 @@ impl<'a> Scanner<'a> {
     pub fn scan_tokens(&mut self) -> &Vec<Token> {
         while !self.end() {
             self.start = self.current;
+            self.column = (self.start - self.line_start) as i64 + 1;
             self.scan_token();
         }
 
@@ -384,6 +411,7 @@ impl<'a> Scanner<'a> {
             None,
             self.line,
             self.position + 1,
+            (self.current - self.line_start) as i64 + 1,
         ));
         return &self.tokens;
     }
@@ -0,0 +1,73 @@
+use colored::Colorize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Extension of the file holding a program's expected output, next to the program
+pub const SNAPSHOT_EXTENSION: &str = "out";
+
+/// Finds the snapshot of a program, e.g. `main.out` for `main.qlm`
+pub fn snapshot_path(path: &Path) -> PathBuf {
+    return path.with_extension(SNAPSHOT_EXTENSION);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    Same(String),
+    /// Only in the expected output
+    Removed(String),
+    /// Only in the actual output
+    Added(String),
+}
+
+impl fmt::Display for DiffLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            DiffLine::Same(line) => write!(f, "  {}", line),
+            DiffLine::Removed(line) => write!(f, "{}", format!("- {}", line).red()),
+            DiffLine::Added(line) => write!(f, "{}", format!("+ {}", line).green()),
+        };
+    }
+}
+
+/// Compares two outputs line by line
+///
+/// Uses the longest common subsequence of lines, so a line that was added or removed
+/// doesn't make every line after it differ.
+///
+/// ### Returns
+/// `Vec<DiffLine>` - the lines of both outputs in order, marked by where they appear
+pub fn diff(expected: &str, actual: &str) -> Vec<DiffLine> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // common[i][j] is the length of the longest common subsequence of expected[i..] and actual[j..]
+    let mut common = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() && j < actual.len() {
+        if expected[i] == actual[j] {
+            lines.push(DiffLine::Same(expected[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            lines.push(DiffLine::Removed(expected[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(actual[j].to_string()));
+            j += 1;
+        }
+    }
+    lines.extend(expected[i..].iter().map(|line| DiffLine::Removed(line.to_string())));
+    lines.extend(actual[j..].iter().map(|line| DiffLine::Added(line.to_string())));
+    return lines;
+}
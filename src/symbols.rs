@@ -0,0 +1,165 @@
+use crate::stack::Stack;
+use crate::token::Token;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SymbolKind {
+    Variable,
+    Parameter,
+    Function,
+    Class,
+}
+
+/// A name declared in the program
+#[derive(Debug, Clone)]
+pub struct Declaration {
+    pub name: Token,
+    pub kind: SymbolKind,
+    /// Parameters of a function, used for signatures
+    pub params: Vec<String>,
+    /// Whether it is declared at the top level of the program
+    pub global: bool,
+    /// Declaration in an outer (non-global) scope with the same name, which this one hides
+    pub shadows: Option<usize>,
+}
+
+/// A use of a name
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub name: Token,
+    /// Index of the declaration it uses (`None` for natives and undeclared names)
+    pub declaration: Option<usize>,
+    /// Whether the value is read, rather than only assigned to
+    pub read: bool,
+}
+
+impl Declaration {
+    /// Describes the declaration the way it is written in source code
+    pub fn signature(&self) -> String {
+        return match self.kind {
+            SymbolKind::Variable => format!("rakho {}", self.name.lexeme),
+            SymbolKind::Parameter => format!("(parameter) {}", self.name.lexeme),
+            SymbolKind::Function => {
+                format!("kaam {}({})", self.name.lexeme, self.params.join(", "))
+            }
+            SymbolKind::Class => format!("jamat {}", self.name.lexeme),
+        };
+    }
+}
+
+/// Records where names are declared and used while the `Resolver` walks the program
+///
+/// Scopes mirror the resolver's, so every use is linked to the declaration the
+/// interpreter will read at runtime. Uses of globals are linked once the whole
+/// program has been resolved, since globals can be used before they are declared.
+pub struct SymbolTable {
+    pub declarations: Vec<Declaration>,
+    pub references: Vec<Reference>,
+    scopes: Stack<HashMap<String, usize>>,
+    globals: HashMap<String, usize>,
+    unresolved: Vec<usize>,
+}
+
+impl SymbolTable {
+    pub fn init() -> Self {
+        return Self {
+            declarations: Vec::new(),
+            references: Vec::new(),
+            scopes: Stack::new(),
+            globals: HashMap::new(),
+            unresolved: Vec::new(),
+        };
+    }
+
+    pub fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    pub fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    pub fn declare(&mut self, name: &Token, kind: SymbolKind, params: Vec<String>) {
+        let index = self.declarations.len();
+        let shadows = match self.scopes.size() {
+            0 => None,
+            size => (0..size - 1)
+                .rev()
+                .find_map(|i| self.scopes.get(i).unwrap().get(&name.lexeme).copied()),
+        };
+        self.declarations.push(Declaration {
+            name: name.clone(),
+            kind,
+            params,
+            global: self.scopes.is_empty(),
+            shadows,
+        });
+        match self.scopes.peek_mut() {
+            Some(scope) => {
+                scope.insert(name.lexeme.to_string(), index);
+            }
+            None => {
+                // Redeclared globals keep pointing at their first declaration
+                self.globals.entry(name.lexeme.to_string()).or_insert(index);
+            }
+        }
+    }
+
+    /// Records a use of a name
+    ///
+    /// ### Arguments
+    /// `name` - the name used
+    /// `read` - whether its value is read, `false` for an assignment
+    pub fn reference(&mut self, name: &Token, read: bool) {
+        let declaration = (0..self.scopes.size())
+            .rev()
+            .find_map(|i| self.scopes.get(i).unwrap().get(&name.lexeme).copied());
+        if declaration.is_none() {
+            self.unresolved.push(self.references.len());
+        }
+        self.references.push(Reference {
+            name: name.clone(),
+            declaration,
+            read,
+        });
+    }
+
+    /// Links uses of globals to their declarations
+    pub fn finish(&mut self) {
+        for i in self.unresolved.drain(..) {
+            let name = &self.references[i].name.lexeme;
+            self.references[i].declaration = self.globals.get(name).copied();
+        }
+    }
+
+    /// Finds the declaration a token at a position declares or refers to
+    ///
+    /// ### Arguments
+    /// `line` - line of the position (starting at 1)
+    /// `column` - column of the position (starting at 1)
+    pub fn declaration_at(&self, line: i64, column: i64) -> Option<usize> {
+        let covers = |token: &Token| {
+            token.line == line
+                && column >= token.column
+                && column <= token.column + token.lexeme.chars().count() as i64
+        };
+        if let Some(index) = self.declarations.iter().position(|d| covers(&d.name)) {
+            return Some(index);
+        }
+        return self
+            .references
+            .iter()
+            .find(|reference| covers(&reference.name))
+            .and_then(|reference| reference.declaration);
+    }
+
+    /// Finds every use of a declaration
+    pub fn references_to(&self, index: usize) -> Vec<&Token> {
+        return self
+            .references
+            .iter()
+            .filter(|reference| reference.declaration == Some(index))
+            .map(|reference| &reference.name)
+            .collect();
+    }
+}
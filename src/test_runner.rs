@@ -0,0 +1,141 @@
+use crate::ast::stmt::Stmt;
+use crate::error::{ErrorReporter, ErrorType, ReportedError};
+use crate::interpreter::Interpreter;
+use crate::literal::Literal;
+use crate::native::assert::{AssertEqualFn, AssertNotEqualFn, AssertThrowsFn};
+use crate::parser::Parser;
+use crate::resolver::Resolver;
+use crate::scanner::Scanner;
+use crate::token::Token;
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+/// Test functions are top level functions without parameters whose name starts with this
+pub const TEST_PREFIX: &str = "test_";
+
+/// Files searched for tests in directories end with this
+pub const TEST_FILE_SUFFIX: &str = "_test.qlm";
+
+/// Output written by `bolo`, kept in memory instead of printed
+#[derive(Clone)]
+pub struct CapturedOutput {
+    buffer: Rc<RefCell<Vec<u8>>>,
+}
+
+impl CapturedOutput {
+    pub fn init() -> Self {
+        return Self {
+            buffer: Rc::new(RefCell::new(Vec::new())),
+        };
+    }
+
+    pub fn contents(&self) -> String {
+        return String::from_utf8_lossy(&self.buffer.borrow()).to_string();
+    }
+}
+
+impl Write for CapturedOutput {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.borrow_mut().extend_from_slice(buf);
+        return Ok(buf.len());
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        return Ok(());
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    Passed,
+    Failed {
+        message: String,
+        line: i64,
+        /// What the test printed before it failed
+        output: String,
+    },
+}
+
+fn parse(source: &str) -> Result<Vec<Stmt>, ReportedError> {
+    let mut reporter = ErrorReporter::init_quiet();
+    let mut scanner = Scanner::init(source, &mut reporter);
+    let tokens = scanner.scan_tokens().clone();
+    if let Some(error) = reporter.errors.first() {
+        return Err(error.clone());
+    }
+    return match Parser::init(&tokens).parse() {
+        Ok(statements) => Ok(statements),
+        Err(e) => {
+            reporter.error_token(&e.token, &e.message, ErrorType::Syntax);
+            Err(reporter.errors[0].clone())
+        }
+    };
+}
+
+/// Finds the test functions in a file
+///
+/// ### Returns
+/// `Result<Vec<Token>, ReportedError>` - the names of the tests in the order they're declared,
+/// or the syntax error in the file
+pub fn test_names(source: &str) -> Result<Vec<Token>, ReportedError> {
+    let names = parse(source)?
+        .into_iter()
+        .filter_map(|stmt| match stmt {
+            Stmt::Function { name, params, .. }
+                if params.is_empty() && name.lexeme.starts_with(TEST_PREFIX) =>
+            {
+                Some(name)
+            }
+            _ => None,
+        })
+        .collect();
+    return Ok(names);
+}
+
+/// Defines the assertion natives, which are only available to tests
+pub fn add_assertions(interpreter: &Interpreter) {
+    let globals = interpreter.globals.clone();
+    Interpreter::add_global(globals.clone(), "assert_equal", AssertEqualFn::init());
+    Interpreter::add_global(globals.clone(), "assert_not_equal", AssertNotEqualFn::init());
+    Interpreter::add_global(globals.clone(), "assert_throws", AssertThrowsFn::init());
+}
+
+/// Runs one test of a file in `interpreter`, which should be a fresh one
+///
+/// The top level of the file runs first, so tests can use what it declares,
+/// then the test function is called.
+pub fn run_test(source: &str, name: &Token, interpreter: Rc<RefCell<Interpreter>>) -> Outcome {
+    let output = CapturedOutput::init();
+    let failed = |message: String, line: i64| Outcome::Failed {
+        message,
+        line,
+        output: output.contents(),
+    };
+
+    let mut statements = match parse(source) {
+        Ok(statements) => statements,
+        Err(e) => return failed(e.message, e.line),
+    };
+    interpreter.borrow_mut().output = Box::new(output.clone());
+    add_assertions(&interpreter.borrow());
+
+    let mut resolver = Resolver::init(interpreter.clone());
+    if let Err(e) = resolver.resolve_stmts(&mut statements) {
+        return failed(e.message.clone(), e.token.line);
+    }
+    let mut interpreter = interpreter.borrow_mut();
+    if let Err(e) = interpreter.interpret(statements) {
+        return failed(e.message.clone(), e.token.line);
+    }
+
+    let test = interpreter.globals.borrow().get(name);
+    let callee = match test {
+        Ok(Some(Literal::Callable(callee))) => callee,
+        _ => return failed(format!("'{}' is no longer a kaam.", name.lexeme), name.line),
+    };
+    return match interpreter.call(callee, Vec::new(), name) {
+        Ok(_) => Outcome::Passed,
+        Err(e) => failed(e.message.clone(), e.token.line),
+    };
+}
@@ -59,9 +59,17 @@ pub enum TokenType {
     Increment,
     Decrement,
 
+    Comment,
+
     Eof,
 }
 
+/// Keywords that can be used in programs
+pub const KEYWORDS: [&str; 18] = [
+    "aur", "jamat", "warna", "jhoot", "har", "kaam", "agar", "khali", "ya", "bolo", "wapis",
+    "asli", "yeh", "sach", "rakho", "jabtak", "na", "shamil",
+];
+
 impl TokenType {
     pub fn from_keyword(keyword: &str) -> Option<Self> {
         match keyword {
@@ -98,6 +106,8 @@ pub struct Token {
     pub literal: Option<Literal>,
     pub line: i64,
     pub position: i64,
+    /// Column (in characters, starting at 1) where the token starts
+    pub column: i64,
 }
 
 impl Clone for Token {
@@ -108,6 +118,7 @@ impl Clone for Token {
             literal: self.literal.clone(),
             line: self.line,
             position: self.position,
+            column: self.column,
         };
     }
 }
@@ -119,6 +130,7 @@ impl Token {
         literal: Option<Literal>,
         line: i64,
         position: i64,
+        column: i64,
     ) -> Self {
         return Self {
             token_type,
@@ -126,6 +138,7 @@ impl Token {
             literal,
             line,
             position,
+            column,
         };
     }
 
@@ -136,6 +149,7 @@ impl Token {
             literal: None,
             line: -1,
             position: 0,
+            column: 0,
         };
     }
 
@@ -146,6 +160,7 @@ impl Token {
             literal: token.literal.clone(),
             line: token.line,
             position: token.position,
+            column: token.column,
         };
     }
 